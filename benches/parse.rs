@@ -0,0 +1,67 @@
+//! Benchmarks the text .fnt parser against the one of the 0.2.0 release, which is pulled in as
+//! the `bmfont_parser_old` dev-dependency. Each font has a benchmark group, so Criterion reports
+//! the parsers side by side.
+
+use bmfont_parser::{BMFont, BMFontRef, Format};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+use std::fmt::Write;
+
+const IOSEVKA_FNT: &str = include_str!("../examples/fonts/iosevka.fnt");
+
+/// A font with one char line for each of the 20992 CJK unified ideographs. It has no kernings
+/// and no `info.charset`, which the old parser does not know and prints its state for.
+fn large_font() -> String {
+    let ids = 0x4E00..0x9E00;
+    let mut fnt = String::new();
+    fnt += "info face=\"Large Generated Font\" size=32 bold=0 italic=0 unicode=1 stretchH=100 \
+            smooth=1 aa=1 padding=0,0,0,0 spacing=1,1 outline=0\n";
+    fnt += "common lineHeight=36 base=29 scaleW=4096 scaleH=4096 pages=1 packed=0 alphaChnl=1 \
+            redChnl=0 greenChnl=0 blueChnl=0\n";
+    fnt += "page id=0 file=\"large_0.png\"\n";
+    writeln!(fnt, "chars count={}", ids.len()).unwrap();
+    for (i, id) in ids.enumerate() {
+        writeln!(
+            fnt,
+            "char id={} x={} y={} width=32 height=32 xoffset=0 yoffset=-3 xadvance=32 page=0 chnl=15",
+            id,
+            i % 128 * 32,
+            i / 128 % 128 * 32
+        )
+        .unwrap();
+    }
+    fnt
+}
+
+fn parse(c: &mut Criterion) {
+    let fonts = vec![
+        ("iosevka", IOSEVKA_FNT.to_owned(), "iosevka.png"),
+        ("large", large_font(), "large_0.png"),
+    ];
+    for (name, fnt, page) in fonts {
+        let mut group = c.benchmark_group(name);
+        group.throughput(Throughput::Bytes(fnt.len() as u64));
+        group.bench_function("baseline", |b| {
+            b.iter(|| {
+                bmfont_parser_old::BMFont::from_loaded(
+                    &bmfont_parser_old::Format::BMFont,
+                    black_box(fnt.as_str()),
+                    &[page],
+                )
+                .unwrap()
+            })
+        });
+        group.bench_function("from_loaded", |b| {
+            b.iter(|| {
+                BMFont::from_loaded(&Format::BMFont, black_box(fnt.as_str()), &[page]).unwrap()
+            })
+        });
+        group.bench_function("bmfont_ref", |b| {
+            b.iter(|| BMFontRef::parse(&Format::BMFont, black_box(&fnt)).unwrap())
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
@@ -1,6 +1,6 @@
 use bmfont_parser::{BMFont, Format};
 
-static IOSEVKA_BMFONT: &'static str = include_str!("fonts/iosevka.fnt");
+static IOSEVKA_BMFONT: &str = include_str!("fonts/iosevka.fnt");
 
 fn main() {
     let bmfont =
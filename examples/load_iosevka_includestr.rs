@@ -1,6 +1,6 @@
 use bmfont_parser::{BMFont, Format};
 
-static IOSEVKA_SFL: &'static str = include_str!("fonts/iosevka.sfl");
+static IOSEVKA_SFL: &str = include_str!("fonts/iosevka.sfl");
 
 fn main() {
     let bmfont =
@@ -0,0 +1,12 @@
+use bmfont_parser::{BMFontRef, Format};
+
+static IOSEVKA_BMFONT: &str = include_str!("fonts/iosevka.fnt");
+
+fn main() {
+    let bmfont = match BMFontRef::parse(&Format::BMFont, IOSEVKA_BMFONT) {
+        Ok(bmfont) => bmfont,
+        Err(_) => panic!("Failed to load iosevka.fnt"),
+    };
+
+    println!("bmfont: {}", bmfont);
+}
@@ -0,0 +1,188 @@
+//! Converts bitmap fonts between the formats supported by `bmfont_parser`.
+//!
+//! ```text
+//! bmfont-convert <input> <output> [--from <format>] [--to <format>]
+//! ```
+//!
+//! The input format is detected from the contents of the input file unless `--from` is given,
+//! and the output format is chosen from the extension of the output file unless `--to` is given.
+//! Page image paths are rewritten to be relative to the output file.
+
+use bmfont_parser::{BMFont, Format};
+
+use std::env;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Component, Path, PathBuf};
+use std::process;
+
+const USAGE: &str = "Usage: bmfont-convert <input> <output> [--from <format>] [--to <format>]
+
+Formats:
+    sfl     FontBuilder .sfl
+    text    AngelCode BMFont text .fnt
+    binary  AngelCode BMFont binary .fnt
+    xml     AngelCode BMFont XML .fnt
+    json    msdf-bmfont-xml JSON";
+
+struct Arguments {
+    input: PathBuf,
+    output: PathBuf,
+    from: Option<Format>,
+    to: Option<Format>,
+}
+
+fn main() {
+    let arguments = match parse_arguments(env::args().skip(1)) {
+        Ok(Some(arguments)) => arguments,
+        Ok(None) => {
+            println!("Converts bitmap fonts between formats.\n\n{}", USAGE);
+            return;
+        }
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(message) = convert(&arguments) {
+        eprintln!("bmfont-convert: {}", message);
+        process::exit(1);
+    }
+}
+
+/// Returns `None` when the usage was asked for with `-h` or `--help`.
+fn parse_arguments<I: Iterator<Item = String>>(mut args: I) -> Result<Option<Arguments>, String> {
+    let mut paths = Vec::new();
+    let mut from = None;
+    let mut to = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--from" | "--to" => {
+                let format = match args.next() {
+                    Some(name) => parse_format(&name)?,
+                    None => return Err(format!("Missing format after {}", arg)),
+                };
+                if arg == "--from" {
+                    from = Some(format);
+                } else {
+                    to = Some(format);
+                }
+            }
+            "-h" | "--help" => return Ok(None),
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+
+    match (paths.pop(), paths.pop(), paths.is_empty()) {
+        (Some(output), Some(input), true) => Ok(Some(Arguments {
+            input,
+            output,
+            from,
+            to,
+        })),
+        _ => Err("Expected an input and an output path".to_owned()),
+    }
+}
+
+fn parse_format(name: &str) -> Result<Format, String> {
+    match name {
+        "sfl" => Ok(Format::SFL),
+        "text" | "fnt" => Ok(Format::BMFont),
+        "binary" => Ok(Format::BMFontBinary),
+        "xml" => Ok(Format::BMFontXml),
+        "json" => Ok(Format::BMFontJson),
+        _ => Err(format!("Unknown format \"{}\"", name)),
+    }
+}
+
+fn format_from_extension(path: &Path) -> Result<Format, String> {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("sfl") => Ok(Format::SFL),
+        Some("fnt") | Some("txt") => Ok(Format::BMFont),
+        Some("xml") => Ok(Format::BMFontXml),
+        Some("json") => Ok(Format::BMFontJson),
+        _ => Err(format!(
+            "Unable to choose an output format for {}, use --to",
+            path.display()
+        )),
+    }
+}
+
+fn convert(arguments: &Arguments) -> Result<(), String> {
+    let to = match arguments.to {
+        Some(format) => format,
+        None => format_from_extension(&arguments.output)?,
+    };
+
+    let input = arguments.input.display();
+    let mut bmfont = match &arguments.from {
+        Some(format) => BMFont::from_path(format, &arguments.input),
+        None => BMFont::from_path_auto(&arguments.input),
+    }
+    .map_err(|e| format!("Failed to load {}: {}", input, e))?;
+
+    let output_dir = arguments.output.parent().unwrap_or_else(|| Path::new(""));
+    for page in bmfont.pages.iter_mut() {
+        page.image_path = relative_path(output_dir, &page.image_path)?;
+    }
+
+    // Write into memory first, so that a failed conversion does not leave a broken file behind
+    let mut contents = Vec::new();
+    bmfont
+        .write(&to, &mut contents)
+        .map_err(|e| format!("Failed to convert {}: {}", input, e))?;
+
+    let output = arguments.output.display();
+    let file = File::create(&arguments.output)
+        .map_err(|e| format!("Failed to create {}: {}", output, e))?;
+    let mut writer = BufWriter::new(file);
+    writer
+        .write_all(&contents)
+        .and_then(|_| writer.flush())
+        .map_err(|e| format!("Failed to write {}: {}", output, e))
+}
+
+/// Returns the path of `target` relative to the directory `base`.
+fn relative_path(base: &Path, target: &Path) -> Result<PathBuf, String> {
+    let base = absolute(base)?;
+    let target = absolute(target)?;
+
+    let common = base
+        .components()
+        .zip(target.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    if common == 0 {
+        // Nothing in common (e.g. different drives), so the path can only be absolute
+        return Ok(target);
+    }
+
+    let mut relative = PathBuf::new();
+    for _ in base.components().skip(common) {
+        relative.push("..");
+    }
+    for component in target.components().skip(common) {
+        relative.push(component);
+    }
+    Ok(relative)
+}
+
+/// Returns `path` as an absolute path without `.` and `..` components.
+fn absolute(path: &Path) -> Result<PathBuf, String> {
+    let current_dir =
+        env::current_dir().map_err(|e| format!("Unable to get current directory: {}", e))?;
+    let mut absolute = PathBuf::new();
+    for component in current_dir.join(path).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                absolute.pop();
+            }
+            component => absolute.push(component),
+        }
+    }
+    Ok(absolute)
+}
@@ -0,0 +1,263 @@
+//! Parser for the binary (version 3) .fnt format of AngelCode BMFont.
+//!
+//! The file starts with the magic `BMF` followed by the version byte, after which come blocks
+//! of the form `[type: u8][size: u32][contents]`, all numbers being little-endian. The bit
+//! numbering of the bitfields follows BMFont's documentation, where bit 0 is the most
+//! significant bit.
+
+use crate::encoding::decode;
+use crate::{semantic_err, syntax_err, unsupported_err, BMFontError, ParseOptions};
+use crate::{BMCharacter, BMFont, CommonDetails, DeclaredCounts, InfoDetails, Page};
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+pub(crate) const MAGIC: &[u8] = b"BMF";
+pub(crate) const VERSION: u8 = 3;
+
+pub(crate) const INFO_SMOOTH: u8 = 0x80;
+pub(crate) const INFO_UNICODE: u8 = 0x40;
+pub(crate) const INFO_ITALIC: u8 = 0x20;
+pub(crate) const INFO_BOLD: u8 = 0x10;
+pub(crate) const COMMON_PACKED: u8 = 0x01;
+
+/// Windows charset ids and the names BMFont uses for them in the text format.
+const CHARSETS: &[(u8, &str)] = &[
+    (0, "ANSI"),
+    (1, "DEFAULT"),
+    (2, "SYMBOL"),
+    (77, "MAC"),
+    (128, "SHIFTJIS"),
+    (129, "HANGUL"),
+    (130, "JOHAB"),
+    (134, "GB2312"),
+    (136, "CHINESEBIG5"),
+    (161, "GREEK"),
+    (162, "TURKISH"),
+    (163, "VIETNAMESE"),
+    (177, "HEBREW"),
+    (178, "ARABIC"),
+    (186, "BALTIC"),
+    (204, "RUSSIAN"),
+    (222, "THAI"),
+    (238, "EASTEUROPE"),
+    (255, "OEM"),
+];
+
+/// Returns the text-format name of the given binary charset id.
+pub(crate) fn charset_name(id: u8) -> String {
+    match CHARSETS.iter().find(|(charset_id, _)| *charset_id == id) {
+        Some((_, name)) => (*name).to_owned(),
+        None => id.to_string(),
+    }
+}
+
+/// Returns the binary charset id of the given text-format charset name.
+pub(crate) fn charset_id(name: &str) -> u8 {
+    match CHARSETS
+        .iter()
+        .find(|(_, charset_name)| *charset_name == name)
+    {
+        Some((id, _)) => *id,
+        None => name.parse().unwrap_or(0),
+    }
+}
+
+pub(crate) fn load(contents: &[u8], options: &mut ParseOptions) -> Result<BMFont, BMFontError> {
+    let mut reader = ByteReader::new(contents);
+
+    if reader.bytes(MAGIC.len())? != MAGIC {
+        return syntax_err("Could not find the \"BMF\" magic");
+    }
+    let version = reader.u8()?;
+    if version != VERSION {
+        return unsupported_err(format!("Unsupported binary .fnt version {}", version));
+    }
+
+    let mut font_name = String::new();
+    let mut size = 0;
+    let mut info_details = None;
+    let mut line_height = 0;
+    let mut common_details = None;
+    let mut pages = Vec::new();
+    let mut chars = HashMap::new();
+    let mut declared_counts = DeclaredCounts::default();
+    let mut kernings = HashMap::new();
+
+    while !reader.is_finished() {
+        let block_type = reader.u8()?;
+        let block_size = reader.u32()? as usize;
+        let mut block = ByteReader::new(reader.bytes(block_size)?);
+
+        match block_type {
+            1 => {
+                size = u32::from(block.i16()?.unsigned_abs());
+                let bits = block.u8()?;
+                let charset = block.u8()?;
+                let mut det = InfoDetails {
+                    bold: u32::from(bits & INFO_BOLD != 0),
+                    italic: u32::from(bits & INFO_ITALIC != 0),
+                    charset: String::new(),
+                    unicode: u32::from(bits & INFO_UNICODE != 0),
+                    stretch_h: u32::from(block.u16()?),
+                    smooth: u32::from(bits & INFO_SMOOTH != 0),
+                    aa: u32::from(block.u8()?),
+                    padding: [0; 4],
+                    spacing: [0; 2],
+                    outline: 0,
+                    extra: BTreeMap::new(),
+                };
+                if det.unicode == 0 {
+                    det.charset = charset_name(charset);
+                }
+                for item in det.padding.iter_mut() {
+                    *item = u32::from(block.u8()?);
+                }
+                for item in det.spacing.iter_mut() {
+                    *item = u32::from(block.u8()?);
+                }
+                det.outline = u32::from(block.u8()?);
+                font_name = block.string(options)?;
+                info_details = Some(det);
+            }
+            2 => {
+                line_height = u32::from(block.u16()?);
+                let mut com = CommonDetails {
+                    base: u32::from(block.u16()?),
+                    scale_w: u32::from(block.u16()?),
+                    scale_h: u32::from(block.u16()?),
+                    pages_count: u32::from(block.u16()?),
+                    packed: 0,
+                    alpha_channel: 0,
+                    red_channel: 0,
+                    green_channel: 0,
+                    blue_channel: 0,
+                    extra: BTreeMap::new(),
+                };
+                com.packed = u32::from(block.u8()? & COMMON_PACKED != 0);
+                com.alpha_channel = u32::from(block.u8()?);
+                com.red_channel = u32::from(block.u8()?);
+                com.green_channel = u32::from(block.u8()?);
+                com.blue_channel = u32::from(block.u8()?);
+                common_details = Some(com);
+            }
+            3 => {
+                while !block.is_finished() {
+                    pages.push(Page {
+                        id: pages.len() as u32,
+                        image_path: PathBuf::from(block.string(options)?),
+                        extra: BTreeMap::new(),
+                    });
+                }
+            }
+            4 => {
+                while !block.is_finished() {
+                    let c = BMCharacter {
+                        id: block.u32()?,
+                        x: u32::from(block.u16()?),
+                        y: u32::from(block.u16()?),
+                        width: u32::from(block.u16()?),
+                        height: u32::from(block.u16()?),
+                        xoffset: i32::from(block.i16()?),
+                        yoffset: i32::from(block.i16()?),
+                        xadvance: i32::from(block.i16()?),
+                        page: u32::from(block.u8()?),
+                        channel: u32::from(block.u8()?),
+                        extra: BTreeMap::new(),
+                    };
+                    if let Some(old) = chars.insert(c.id, c) {
+                        declared_counts.duplicate_char(old.id, None, options);
+                    }
+                }
+            }
+            5 => {
+                while !block.is_finished() {
+                    let first = block.u32()?;
+                    let second = block.u32()?;
+                    let amount = i32::from(block.i16()?);
+                    kernings.insert((first, second), amount);
+                }
+            }
+            _ => return syntax_err(format!("Unknown block type {}", block_type)),
+        }
+    }
+
+    if info_details.is_none() {
+        return semantic_err("Could not find the info block");
+    }
+    if common_details.is_none() {
+        return semantic_err("Could not find the common block");
+    }
+    if pages.is_empty() {
+        return semantic_err("Could not find any pages!");
+    }
+
+    Ok(BMFont {
+        font_name,
+        size,
+        info_details,
+        line_height,
+        common_details,
+        distance_field: None,
+        pages,
+        chars,
+        kernings,
+        declared_counts,
+    })
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    cursor: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn new(bytes: &'a [u8]) -> ByteReader<'a> {
+        ByteReader { bytes, cursor: 0 }
+    }
+
+    fn bytes(&mut self, amount: usize) -> Result<&'a [u8], BMFontError> {
+        if self.bytes.len() - self.cursor < amount {
+            syntax_err("Unexpected end of binary .fnt block")
+        } else {
+            let bytes = &self.bytes[self.cursor..self.cursor + amount];
+            self.cursor += amount;
+            Ok(bytes)
+        }
+    }
+
+    fn u8(&mut self) -> Result<u8, BMFontError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, BMFontError> {
+        let bytes = self.bytes(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn i16(&mut self) -> Result<i16, BMFontError> {
+        let bytes = self.bytes(2)?;
+        Ok(i16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, BMFontError> {
+        let bytes = self.bytes(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Reads a null-terminated string.
+    fn string(&mut self, options: &mut ParseOptions) -> Result<String, BMFontError> {
+        let rest = &self.bytes[self.cursor..];
+        let len = match rest.iter().position(|b| *b == 0) {
+            Some(len) => len,
+            None => return syntax_err("Unterminated string in binary .fnt block"),
+        };
+        let text = decode(&rest[..len], None, options).into_owned();
+        self.cursor += len + 1;
+        Ok(text)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.cursor == self.bytes.len()
+    }
+}
@@ -0,0 +1,161 @@
+use crate::bmfont_binary_parser::{
+    charset_id, COMMON_PACKED, INFO_BOLD, INFO_ITALIC, INFO_SMOOTH, INFO_UNICODE, MAGIC, VERSION,
+};
+use crate::bmfont_writer::{common_details, info_details};
+use crate::{unsupported_err, BMFont, BMFontError};
+
+use std::convert::TryFrom;
+use std::io::Write;
+
+pub(crate) fn write<W: Write>(bmfont: &BMFont, mut w: W) -> Result<(), BMFontError> {
+    let mut bytes = MAGIC.to_vec();
+    bytes.push(VERSION);
+
+    let det = info_details(bmfont);
+    let mut info = Vec::new();
+    info.extend_from_slice(&number::<i16, _>(bmfont.size, "info.size")?.to_le_bytes());
+    let mut bits = 0;
+    if det.smooth != 0 {
+        bits |= INFO_SMOOTH;
+    }
+    if det.unicode != 0 {
+        bits |= INFO_UNICODE;
+    }
+    if det.italic != 0 {
+        bits |= INFO_ITALIC;
+    }
+    if det.bold != 0 {
+        bits |= INFO_BOLD;
+    }
+    info.push(bits);
+    info.push(charset_id(&det.charset));
+    info.extend_from_slice(&number::<u16, _>(det.stretch_h, "info.stretchH")?.to_le_bytes());
+    info.push(number(det.aa, "info.aa")?);
+    for padding in det.padding.iter() {
+        info.push(number(*padding, "info.padding")?);
+    }
+    for spacing in det.spacing.iter() {
+        info.push(number(*spacing, "info.spacing")?);
+    }
+    info.push(number(det.outline, "info.outline")?);
+    info.extend_from_slice(&string(&bmfont.font_name)?);
+    block(&mut bytes, 1, &info)?;
+
+    let com = common_details(bmfont);
+    let mut common = Vec::new();
+    for (value, name) in [
+        (bmfont.line_height, "common.lineHeight"),
+        (com.base, "common.base"),
+        (com.scale_w, "common.scaleW"),
+        (com.scale_h, "common.scaleH"),
+        (com.pages_count, "common.pages"),
+    ]
+    .iter()
+    {
+        common.extend_from_slice(&number::<u16, _>(*value, name)?.to_le_bytes());
+    }
+    common.push(if com.packed != 0 { COMMON_PACKED } else { 0 });
+    common.push(number(com.alpha_channel, "common.alphaChnl")?);
+    common.push(number(com.red_channel, "common.redChnl")?);
+    common.push(number(com.green_channel, "common.greenChnl")?);
+    common.push(number(com.blue_channel, "common.blueChnl")?);
+    block(&mut bytes, 2, &common)?;
+
+    let mut pages: Vec<_> = bmfont.pages.iter().collect();
+    pages.sort_by_key(|page| page.id);
+    let mut page_names = Vec::new();
+    for (idx, page) in pages.iter().enumerate() {
+        if page.id != idx as u32 {
+            return unsupported_err(
+                "Page ids must be consecutive starting from 0 in the binary format",
+            );
+        }
+        let name = string(&page.image_path.to_string_lossy())?;
+        if page_names.len() != idx * name.len() {
+            return unsupported_err(
+                "Page file names must all be of the same length in the binary format",
+            );
+        }
+        page_names.extend_from_slice(&name);
+    }
+    block(&mut bytes, 3, &page_names)?;
+
+    let mut chars: Vec<_> = bmfont.chars.values().collect();
+    chars.sort_by_key(|c| c.id);
+    let mut char_bytes = Vec::with_capacity(chars.len() * 20);
+    for c in chars {
+        char_bytes.extend_from_slice(&c.id.to_le_bytes());
+        for (value, name) in [
+            (c.x, "x"),
+            (c.y, "y"),
+            (c.width, "width"),
+            (c.height, "height"),
+        ]
+        .iter()
+        {
+            let value = number::<u16, _>(*value, &format!("char.{}", name))?;
+            char_bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        for (value, name) in [
+            (c.xoffset, "xoffset"),
+            (c.yoffset, "yoffset"),
+            (c.xadvance, "xadvance"),
+        ]
+        .iter()
+        {
+            let value = number::<i16, _>(*value, &format!("char.{}", name))?;
+            char_bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        char_bytes.push(number(c.page, "char.page")?);
+        char_bytes.push(number(c.channel, "char.chnl")?);
+    }
+    block(&mut bytes, 4, &char_bytes)?;
+
+    if !bmfont.kernings.is_empty() {
+        let mut kernings: Vec<_> = bmfont.kernings.iter().collect();
+        kernings.sort();
+        let mut kerning_bytes = Vec::with_capacity(kernings.len() * 10);
+        for ((first, second), amount) in kernings {
+            kerning_bytes.extend_from_slice(&first.to_le_bytes());
+            kerning_bytes.extend_from_slice(&second.to_le_bytes());
+            kerning_bytes
+                .extend_from_slice(&number::<i16, _>(*amount, "kerning.amount")?.to_le_bytes());
+        }
+        block(&mut bytes, 5, &kerning_bytes)?;
+    }
+
+    w.write_all(&bytes)?;
+    Ok(())
+}
+
+fn block(bytes: &mut Vec<u8>, block_type: u8, contents: &[u8]) -> Result<(), BMFontError> {
+    let size = match u32::try_from(contents.len()) {
+        Ok(size) => size,
+        Err(_) => return unsupported_err("Block is too large for the binary format"),
+    };
+    bytes.push(block_type);
+    bytes.extend_from_slice(&size.to_le_bytes());
+    bytes.extend_from_slice(contents);
+    Ok(())
+}
+
+/// Converts `value` into the type used for it in the binary format.
+fn number<T: TryFrom<N>, N: Copy>(value: N, name: &str) -> Result<T, BMFontError> {
+    match T::try_from(value) {
+        Ok(value) => Ok(value),
+        Err(_) => unsupported_err(format!(
+            "Value of {} does not fit in the binary format",
+            name
+        )),
+    }
+}
+
+/// Returns `text` as a null-terminated string.
+fn string(text: &str) -> Result<Vec<u8>, BMFontError> {
+    if text.contains('\0') {
+        return unsupported_err("Strings in the binary format can not contain null characters");
+    }
+    let mut bytes = text.as_bytes().to_vec();
+    bytes.push(0);
+    Ok(bytes)
+}
@@ -0,0 +1,253 @@
+use crate::bmfont_parser::{CHAR_KEYS, COMMON_KEYS, INFO_KEYS, KERNING_KEYS};
+use crate::json::{self, Value};
+use crate::{result_or, semantic_err, syntax_err, BMFontError, CharId, ParseOptions};
+use crate::{BMCharacter, BMFont, CommonDetails, DeclaredCounts, DistanceField, InfoDetails, Page};
+
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::path::PathBuf;
+
+/// Keys msdf-bmfont-xml writes for each char besides those of the other formats: the glyph index
+/// in the font file and the character itself.
+const JSON_CHAR_KEYS: &[&str] = &["index", "char"];
+
+const DISTANCE_FIELD_KEYS: &[&str] = &["fieldType", "distanceRange"];
+
+pub(crate) fn load(json_contents: &str, options: &mut ParseOptions) -> Result<BMFont, BMFontError> {
+    let font = json::parse(json_contents)?;
+
+    let info = match font.get("info") {
+        Some(info) => info,
+        None => return syntax_err("Could not find \"info\""),
+    };
+    let font_name = string(info, "info", "face")?.unwrap_or_default();
+    let size = number(info, "info", "size", 0)?;
+    let det = InfoDetails {
+        bold: number(info, "info", "bold", 0)?,
+        italic: number(info, "info", "italic", 0)?,
+        charset: charset(info, options)?,
+        unicode: number(info, "info", "unicode", 0)?,
+        stretch_h: number(info, "info", "stretchH", 0)?,
+        smooth: number(info, "info", "smooth", 0)?,
+        aa: number(info, "info", "aa", 0)?,
+        padding: list(info, "info", "padding")?,
+        spacing: list(info, "info", "spacing")?,
+        outline: number(info, "info", "outline", 0)?,
+        extra: extra(info, "info", INFO_KEYS, options)?,
+    };
+
+    let common = match font.get("common") {
+        Some(common) => common,
+        None => return syntax_err("Could not find \"common\""),
+    };
+    let line_height = number(common, "common", "lineHeight", 0)?;
+    let com = CommonDetails {
+        base: number(common, "common", "base", 0)?,
+        scale_w: number(common, "common", "scaleW", 0)?,
+        scale_h: number(common, "common", "scaleH", 0)?,
+        pages_count: number(common, "common", "pages", 0)?,
+        packed: number(common, "common", "packed", 0)?,
+        alpha_channel: number(common, "common", "alphaChnl", 0)?,
+        red_channel: number(common, "common", "redChnl", 0)?,
+        green_channel: number(common, "common", "greenChnl", 0)?,
+        blue_channel: number(common, "common", "blueChnl", 0)?,
+        extra: extra(common, "common", COMMON_KEYS, options)?,
+    };
+
+    let distance_field = match font.get("distanceField") {
+        Some(field) => {
+            extra(field, "distanceField", DISTANCE_FIELD_KEYS, options)?;
+            Some(DistanceField {
+                field_type: string(field, "distanceField", "fieldType")?.unwrap_or_default(),
+                distance_range: number(field, "distanceField", "distanceRange", 0)?,
+            })
+        }
+        None => None,
+    };
+
+    let mut pages = Vec::new();
+    for (idx, page) in array(&font, "pages")?.iter().enumerate() {
+        match page.as_str() {
+            Some(file) => pages.push(Page {
+                id: idx as u32,
+                image_path: PathBuf::from(file),
+                extra: BTreeMap::new(),
+            }),
+            None => return syntax_err("Unable to parse pages value"),
+        }
+    }
+    if pages.is_empty() {
+        return semantic_err("Could not find any pages!");
+    }
+
+    let mut chars = HashMap::new();
+    let mut declared_counts = DeclaredCounts::default();
+    for c in array(&font, "chars")? {
+        let c = BMCharacter {
+            id: number(c, "char", "id", CharId(0))?.0,
+            x: number(c, "char", "x", 0)?,
+            y: number(c, "char", "y", 0)?,
+            width: number(c, "char", "width", 0)?,
+            height: number(c, "char", "height", 0)?,
+            xoffset: number(c, "char", "xoffset", 0)?,
+            yoffset: number(c, "char", "yoffset", 0)?,
+            xadvance: number(c, "char", "xadvance", 0)?,
+            page: number(c, "char", "page", 0)?,
+            channel: number(c, "char", "chnl", 15)?,
+            extra: extra(c, "char", CHAR_KEYS, options)?,
+        };
+        if let Some(old) = chars.insert(c.id, c) {
+            declared_counts.duplicate_char(old.id, None, options);
+        }
+    }
+
+    let mut kernings = HashMap::new();
+    for kerning in array(&font, "kernings")? {
+        extra(kerning, "kerning", KERNING_KEYS, options)?;
+        kernings.insert(
+            (
+                number(kerning, "kerning", "first", 0)?,
+                number(kerning, "kerning", "second", 0)?,
+            ),
+            number(kerning, "kerning", "amount", 0)?,
+        );
+    }
+
+    Ok(BMFont {
+        font_name,
+        size,
+        info_details: Some(det),
+        line_height,
+        common_details: Some(com),
+        distance_field,
+        pages,
+        chars,
+        kernings,
+        declared_counts,
+    })
+}
+
+/// Returns the array `key` of `object`, or an empty slice if it is missing.
+fn array<'a>(object: &'a Value, key: &str) -> Result<&'a [Value], BMFontError> {
+    match object.get(key) {
+        Some(value) => match value.as_array() {
+            Some(values) => Ok(values),
+            None => syntax_err(format!("Unable to parse {} value", key)),
+        },
+        None => Ok(&[]),
+    }
+}
+
+/// Returns the string `key` of `object`, or `None` if it is missing.
+fn string(object: &Value, block: &str, key: &str) -> Result<Option<String>, BMFontError> {
+    match object.get(key) {
+        Some(value) => match value.as_str() {
+            Some(text) => Ok(Some(text.to_owned())),
+            None => syntax_err(format!("Unable to parse {}.{} value", block, key)),
+        },
+        None => Ok(None),
+    }
+}
+
+/// Returns the integer `key` of `object`, or `default` if it is missing.
+fn number<T: TryFrom<i64>>(
+    object: &Value,
+    block: &str,
+    key: &str,
+    default: T,
+) -> Result<T, BMFontError> {
+    match object.get(key) {
+        Some(value) => integer(value, block, key),
+        None => Ok(default),
+    }
+}
+
+/// Flags may also be written as booleans by some tools.
+fn integer<T: TryFrom<i64>>(value: &Value, block: &str, key: &str) -> Result<T, BMFontError> {
+    let text = format!("Unable to parse {}.{} value", block, key);
+    match value {
+        Value::Number(number) if number.fract() == 0.0 => {
+            result_or(T::try_from(*number as i64), text)
+        }
+        Value::Bool(flag) => result_or(T::try_from(i64::from(*flag)), text),
+        _ => syntax_err(text),
+    }
+}
+
+/// Returns the array `key` of `object` as `N` integers, or zeroes if it is missing.
+fn list<const N: usize>(object: &Value, block: &str, key: &str) -> Result<[u32; N], BMFontError> {
+    let mut list = [0; N];
+    if object.get(key).is_some() {
+        let values = array(object, key)?;
+        if values.len() != N {
+            return syntax_err(format!(
+                "Expected {} values in {}.{}, found {}",
+                N,
+                block,
+                key,
+                values.len()
+            ));
+        }
+        for (item, value) in list.iter_mut().zip(values) {
+            *item = integer(value, block, key)?;
+        }
+    }
+    Ok(list)
+}
+
+/// The charset is either a charset name like in the other formats, or an array of the characters
+/// in the font. The array is already described by the chars, so it is not kept.
+fn charset(info: &Value, options: &mut ParseOptions) -> Result<String, BMFontError> {
+    match info.get("charset") {
+        Some(Value::String(charset)) => Ok(charset.clone()),
+        Some(Value::Array(chars)) if chars.iter().all(|c| c.as_str().is_some()) => {
+            options.debug("Skipping the info.charset array of characters", None);
+            Ok(String::new())
+        }
+        Some(_) => syntax_err("Unable to parse info.charset value"),
+        None => Ok(String::new()),
+    }
+}
+
+/// Returns the keys of `object` that are not in `known`, with their values as text. They are an
+/// error in strict mode, otherwise a warning is reported for each of them.
+fn extra(
+    object: &Value,
+    block: &str,
+    known: &[&str],
+    options: &mut ParseOptions,
+) -> Result<BTreeMap<String, String>, BMFontError> {
+    let mut extra = BTreeMap::new();
+    for (key, value) in object.entries() {
+        if known.contains(&key.as_str())
+            || block == "char" && JSON_CHAR_KEYS.contains(&key.as_str())
+        {
+            continue;
+        }
+        if options.strict {
+            return syntax_err(format!("Unknown key {}.{}", block, key));
+        }
+        options.warn(format!("Skipping unknown key {}.{}", block, key), None);
+        if let Some(text) = text(value) {
+            extra.insert(key.clone(), text);
+        }
+    }
+    Ok(extra)
+}
+
+/// Returns a value as it would be written in the text format, with arrays separated by commas.
+/// Objects have no such form.
+fn text(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => Some(String::new()),
+        Value::Bool(flag) => Some(i64::from(*flag).to_string()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::String(text) => Some(text.clone()),
+        Value::Array(values) => values
+            .iter()
+            .map(text)
+            .collect::<Option<Vec<_>>>()
+            .map(|values| values.join(",")),
+        Value::Object(_) => None,
+    }
+}
@@ -0,0 +1,141 @@
+use crate::bmfont_writer::{common_details, info_details};
+use crate::json::escape;
+use crate::{unsupported_err, BMFont, BMFontError, CharId};
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+pub(crate) fn write<W: Write>(bmfont: &BMFont, mut w: W) -> Result<(), BMFontError> {
+    if let Some(page) = bmfont.pages.iter().find(|page| !page.extra.is_empty()) {
+        return unsupported_err(format!(
+            "Unable to write the unknown keys of page {} into a .json file, which only has the file of each page",
+            page.id
+        ));
+    }
+
+    let mut chars: Vec<_> = bmfont.chars.values().collect();
+    chars.sort_by_key(|c| c.id);
+
+    writeln!(w, "{{")?;
+
+    let pages: Vec<String> = bmfont
+        .pages
+        .iter()
+        .map(|page| escape(&page.image_path.to_string_lossy()))
+        .collect();
+    writeln!(w, "  \"pages\": [{}],", pages.join(", "))?;
+
+    writeln!(w, "  \"chars\": [")?;
+    for (idx, c) in chars.iter().enumerate() {
+        let character = std::char::from_u32(c.id)
+            .map(|character| format!("\"char\": {}, ", escape(&character.to_string())))
+            .unwrap_or_default();
+        writeln!(
+            w,
+            "    {{\"id\": {}, {}\"width\": {}, \"height\": {}, \"xoffset\": {}, \"yoffset\": {}, \"xadvance\": {}, \"chnl\": {}, \"x\": {}, \"y\": {}, \"page\": {}{}}}{}",
+            CharId(c.id),
+            character,
+            c.width,
+            c.height,
+            c.xoffset,
+            c.yoffset,
+            c.xadvance,
+            c.channel,
+            c.x,
+            c.y,
+            c.page,
+            extra(&c.extra),
+            if idx + 1 < chars.len() { "," } else { "" }
+        )?;
+    }
+    writeln!(w, "  ],")?;
+
+    // A charset name is kept as it is, otherwise the charset is the characters of the font
+    let det = info_details(bmfont);
+    let charset = if det.charset.is_empty() {
+        let characters: Vec<String> = chars
+            .iter()
+            .filter_map(|c| std::char::from_u32(c.id))
+            .map(|character| escape(&character.to_string()))
+            .collect();
+        format!("[{}]", characters.join(", "))
+    } else {
+        escape(&det.charset)
+    };
+    writeln!(
+        w,
+        "  \"info\": {{\"face\": {}, \"size\": {}, \"bold\": {}, \"italic\": {}, \"charset\": {}, \"unicode\": {}, \"stretchH\": {}, \"smooth\": {}, \"aa\": {}, \"padding\": [{}, {}, {}, {}], \"spacing\": [{}, {}], \"outline\": {}{}}},",
+        escape(&bmfont.font_name),
+        bmfont.size,
+        det.bold,
+        det.italic,
+        charset,
+        det.unicode,
+        det.stretch_h,
+        det.smooth,
+        det.aa,
+        det.padding[0],
+        det.padding[1],
+        det.padding[2],
+        det.padding[3],
+        det.spacing[0],
+        det.spacing[1],
+        det.outline,
+        extra(&det.extra)
+    )?;
+
+    let com = common_details(bmfont);
+    writeln!(
+        w,
+        "  \"common\": {{\"lineHeight\": {}, \"base\": {}, \"scaleW\": {}, \"scaleH\": {}, \"pages\": {}, \"packed\": {}, \"alphaChnl\": {}, \"redChnl\": {}, \"greenChnl\": {}, \"blueChnl\": {}{}}},",
+        bmfont.line_height,
+        com.base,
+        com.scale_w,
+        com.scale_h,
+        com.pages_count,
+        com.packed,
+        com.alpha_channel,
+        com.red_channel,
+        com.green_channel,
+        com.blue_channel,
+        extra(&com.extra)
+    )?;
+
+    if let Some(field) = &bmfont.distance_field {
+        writeln!(
+            w,
+            "  \"distanceField\": {{\"fieldType\": {}, \"distanceRange\": {}}},",
+            escape(&field.field_type),
+            field.distance_range
+        )?;
+    }
+
+    let mut kernings: Vec<_> = bmfont.kernings.iter().collect();
+    kernings.sort();
+    let kernings: Vec<String> = kernings
+        .iter()
+        .map(|((first, second), amount)| {
+            format!(
+                "    {{\"first\": {}, \"second\": {}, \"amount\": {}}}",
+                first, second, amount
+            )
+        })
+        .collect();
+    if kernings.is_empty() {
+        writeln!(w, "  \"kernings\": []")?;
+    } else {
+        writeln!(w, "  \"kernings\": [\n{}\n  ]", kernings.join(",\n"))?;
+    }
+
+    writeln!(w, "}}")?;
+    Ok(())
+}
+
+/// Returns the unknown keys of a block as `, "key": "value"` members.
+fn extra(extra: &BTreeMap<String, String>) -> String {
+    let mut text = String::new();
+    for (key, value) in extra.iter() {
+        text += &format!(", {}: {}", escape(key), escape(value));
+    }
+    text
+}
@@ -1,24 +1,262 @@
-use crate::parser::Parser;
-use crate::{err, result_or};
-use crate::{BMCharacter, BMFont, CommonDetails, InfoDetails, Page};
+use crate::encoding::decode;
+use crate::parser::{Parser, Unexpected};
+use crate::{expect_or, semantic_err, BMFontError, CharId, ParseOptions, Position};
+use crate::{
+    BMCharacter, BMFont, BMFontRef, CommonDetails, DeclaredCounts, InfoDetailsRef, PageRef,
+};
 
-use std::collections::HashMap;
-use std::io::Error;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::io::BufRead;
 
-pub(crate) fn load<T: Into<String>>(sfl_contents: T) -> Result<BMFont, Error> {
-    let content = sfl_contents.into();
-    let mut parser = Parser::new(&content);
+/// The keys of each block that are known, in the order they are written.
+pub(crate) const INFO_KEYS: &[&str] = &[
+    "face", "size", "bold", "italic", "charset", "unicode", "stretchH", "smooth", "aa", "padding",
+    "spacing", "outline",
+];
 
+pub(crate) const COMMON_KEYS: &[&str] = &[
+    "lineHeight",
+    "base",
+    "scaleW",
+    "scaleH",
+    "pages",
+    "packed",
+    "alphaChnl",
+    "redChnl",
+    "greenChnl",
+    "blueChnl",
+];
+
+pub(crate) const PAGE_KEYS: &[&str] = &["id", "file"];
+
+pub(crate) const CHAR_KEYS: &[&str] = &[
+    "id", "x", "y", "width", "height", "xoffset", "yoffset", "xadvance", "page", "chnl",
+];
+
+pub(crate) const KERNING_KEYS: &[&str] = &["first", "second", "amount"];
+
+pub(crate) fn load<'a>(
+    content: &'a str,
+    options: &mut ParseOptions,
+) -> Result<BMFontRef<'a>, BMFontError> {
+    let mut parser = Parser::new(content);
+    let mut font = header(&mut parser, options)?;
+
+    let mut body = Body::new();
+    parser.skip_whitespace();
+    while !parser.is_finished() {
+        body.line(&mut parser, options)?;
+        parser.skip_whitespace();
+    }
+    font.declared_counts = body.counts(options);
+    font.chars = body.chars;
+    font.kernings = body.kernings;
+    Ok(font)
+}
+
+/// Parses a text .fnt file from `reader` one line at a time, after the info, common and page
+/// lines, which are parsed together.
+pub(crate) fn load_lines<R: BufRead>(
+    reader: R,
+    options: &mut ParseOptions,
+) -> Result<BMFont, BMFontError> {
+    let mut lines = Lines {
+        reader,
+        number: 0,
+        bytes: Vec::new(),
+    };
+
+    let mut header_text = String::new();
+    let mut next = None;
+    while let Some(line) = lines.next(options)? {
+        let tag = line.split_whitespace().next();
+        if matches!(tag, Some("chars" | "char" | "kernings" | "kerning")) {
+            next = Some(line);
+            break;
+        }
+        header_text += &line;
+    }
+    let mut body = Body::new();
+    let mut parser = Parser::new(&header_text);
+    let mut font = BMFont::from(header(&mut parser, options)?);
     parser.skip_whitespace();
-    result_or(parser.expect("info").get(), "Could not parse \"info\"")?;
+    if !parser.is_finished() {
+        return body.unexpected(&parser);
+    }
+
+    loop {
+        let line = match next.take() {
+            Some(line) => line,
+            None => match lines.next(options)? {
+                Some(line) => line,
+                None => break,
+            },
+        };
+        let mut parser = Parser::at_line(&line, lines.number);
+        parser.skip_whitespace();
+        if parser.is_finished() {
+            continue;
+        }
+        body.line(&mut parser, options)?;
+        parser.skip_whitespace();
+        if !parser.is_finished() {
+            return body.unexpected(&parser);
+        }
+    }
+    font.declared_counts = body.counts(options);
+    font.chars = body.chars;
+    font.kernings = body.kernings;
+    Ok(font)
+}
 
-    let mut font_name = String::new();
+/// The chars and kernings blocks after the page lines, which both `load` and `load_lines` parse
+/// one line at a time.
+struct Body {
+    stage: Stage,
+    chars: HashMap<u32, BMCharacter>,
+    kernings: HashMap<(u32, u32), i32>,
+    chars_count: Option<(usize, Position)>,
+    kernings_count: Option<(usize, Position)>,
+    declared: DeclaredCounts,
+}
+
+/// The block of the last line, as the blocks must appear in this order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Stage {
+    Pages,
+    Chars,
+    Kernings,
+}
+
+impl Body {
+    fn new() -> Body {
+        Body {
+            stage: Stage::Pages,
+            chars: HashMap::new(),
+            kernings: HashMap::new(),
+            chars_count: None,
+            kernings_count: None,
+            declared: DeclaredCounts::default(),
+        }
+    }
+
+    /// Parses the line at `parser`, which must be one the current block may be followed by.
+    fn line(&mut self, parser: &mut Parser, options: &mut ParseOptions) -> Result<(), BMFontError> {
+        let start = parser.clone();
+        let position = parser.position();
+        let tag = match self.stage {
+            Stage::Pages => parser
+                .expect("chars count=")
+                .or("char", parser)
+                .or("kernings count=", parser)
+                .or("kerning", parser),
+            Stage::Chars => parser
+                .expect("char")
+                .or("kernings count=", parser)
+                .or("kerning", parser),
+            Stage::Kernings => parser.expect("kerning"),
+        };
+        match tag.get() {
+            Ok("chars count=") => {
+                self.chars_count = Some((count(parser, "chars")?, position));
+                self.stage = Stage::Chars;
+            }
+            Ok("char") => {
+                let c = char_keys(parser, options)?;
+                if let Some(old) = self.chars.insert(c.id, c) {
+                    self.declared
+                        .duplicate_char(old.id, Some(position), options);
+                }
+                self.stage = Stage::Chars;
+            }
+            Ok("kernings count=") => {
+                self.kernings_count = Some((count(parser, "kernings")?, position));
+                self.stage = Stage::Kernings;
+            }
+            Ok(_) => {
+                let (pair, amount) = kerning_keys(parser, options)?;
+                self.kernings.insert(pair, amount);
+                self.stage = Stage::Kernings;
+            }
+            Err(unexpected) => return Err(unexpected.into_error(self.message(&start))),
+        }
+        Ok(())
+    }
+
+    /// Returns a syntax error for the unexpected text at `parser`.
+    fn unexpected<T>(&self, parser: &Parser) -> Result<T, BMFontError> {
+        parser.error(self.message(parser))
+    }
+
+    fn message(&self, parser: &Parser) -> String {
+        let word = parser
+            .clone()
+            .take_while(|c| !Parser::is_whitespace(c))
+            .to_owned();
+        let block = match self.stage {
+            Stage::Pages => "page",
+            Stage::Chars => "char",
+            Stage::Kernings => "kerning",
+        };
+        format!("Unexpected \"{}\" after the {} lines", word, block)
+    }
+
+    /// Warns about declared counts that do not match what was found, and returns them with the
+    /// duplicate char ids.
+    fn counts(&mut self, options: &mut ParseOptions) -> DeclaredCounts {
+        check_count("chars", self.chars_count, self.chars.len(), options);
+        check_count(
+            "kernings",
+            self.kernings_count,
+            self.kernings.len(),
+            options,
+        );
+        self.declared.chars = self.chars_count.map(|(count, _)| count);
+        self.declared.kernings = self.kernings_count.map(|(count, _)| count);
+        std::mem::take(&mut self.declared)
+    }
+}
+
+/// The lines of a text font, each decoded on its own.
+struct Lines<R> {
+    reader: R,
+    number: usize,
+    bytes: Vec<u8>,
+}
+
+impl<R: BufRead> Lines<R> {
+    fn next(&mut self, options: &mut ParseOptions) -> Result<Option<String>, BMFontError> {
+        self.bytes.clear();
+        if self.reader.read_until(b'\n', &mut self.bytes)? == 0 {
+            return Ok(None);
+        }
+        self.number += 1;
+        let mut bytes = &self.bytes[..];
+        if self.number == 1 {
+            bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+        }
+        let position = Position {
+            line: self.number,
+            column: 1,
+        };
+        Ok(Some(decode(bytes, Some(position), options).into_owned()))
+    }
+}
+
+/// Parses the info, common and page lines, returning a font without chars and kernings.
+fn header<'a>(
+    parser: &mut Parser<'a>,
+    options: &mut ParseOptions,
+) -> Result<BMFontRef<'a>, BMFontError> {
+    parser.skip_whitespace();
+    expect_or(parser.expect("info").get(), "Could not parse \"info\"")?;
+
+    let mut font_name = "";
     let mut size = 0;
-    let mut det = InfoDetails {
+    let mut det = InfoDetailsRef {
         bold: 0,
         italic: 0,
-        charset: String::new(),
+        charset: "",
         unicode: 0,
         stretch_h: 0,
         smooth: 0,
@@ -26,6 +264,7 @@ pub(crate) fn load<T: Into<String>>(sfl_contents: T) -> Result<BMFont, Error> {
         padding: [0; 4],
         spacing: [0; 2],
         outline: 0,
+        extra: BTreeMap::new(),
     };
 
     let mut line_height = 0;
@@ -39,251 +278,155 @@ pub(crate) fn load<T: Into<String>>(sfl_contents: T) -> Result<BMFont, Error> {
         red_channel: 0,
         green_channel: 0,
         blue_channel: 0,
+        extra: BTreeMap::new(),
     };
 
     parser.skip_whitespace();
-    let mut keyword_res;
-    while {
-        keyword_res = parser
-            .expect("face")
-            .or("size", &mut parser)
-            .or("bold", &mut parser)
-            .or("italic", &mut parser)
-            .or("charset", &mut parser)
-            .or("unicode", &mut parser)
-            .or("stretchH", &mut parser)
-            .or("smooth", &mut parser)
-            .or("aa", &mut parser)
-            .or("padding", &mut parser)
-            .or("spacing", &mut parser)
-            .or("outline", &mut parser)
-            .get();
-        keyword_res.is_ok()
-    } {
-        let keyword = keyword_res.unwrap();
-        result_or(
-            parser.expect("=").get(),
-            format!("Unable to get \"=\" after info.{}", keyword),
-        )?;
-        match &*keyword {
+    let mut start = parser.clone();
+    while let Ok(keyword) = parser.expect_key() {
+        match keyword {
             "face" => {
-                font_name = result_or(
-                    parser.expect_ident(),
-                    format!("Unable to parse info.{} value", keyword),
-                )?;
+                font_name = value(parser.expect_ident(), "info", keyword)?;
             }
             "size" => {
-                size = result_or(
-                    parser.expect_number(),
-                    format!("Unable to parse info.{} value", keyword),
-                )?;
+                size = value(parser.expect_number(), "info", keyword)?;
             }
             "bold" => {
-                det.bold = result_or(
-                    parser.expect_number(),
-                    format!("Unable to parse info.{} value", keyword),
-                )?;
+                det.bold = value(parser.expect_number(), "info", keyword)?;
             }
             "italic" => {
-                det.italic = result_or(
-                    parser.expect_number(),
-                    format!("Unable to parse info.{} value", keyword),
-                )?;
+                det.italic = value(parser.expect_number(), "info", keyword)?;
             }
             "charset" => {
-                parser.print_surroundings();
-                det.charset = result_or(
-                    parser.expect_ident(),
-                    format!("Unable to parse info.{} value", keyword),
-                )?;
+                det.charset = value(parser.expect_ident(), "info", keyword)?;
             }
             "unicode" => {
-                det.unicode = result_or(
-                    parser.expect_number(),
-                    format!("Unable to parse info.{} value", keyword),
-                )?;
+                det.unicode = value(parser.expect_number(), "info", keyword)?;
             }
             "stretchH" => {
-                det.stretch_h = result_or(
-                    parser.expect_number(),
-                    format!("Unable to parse info.{} value", keyword),
-                )?;
+                det.stretch_h = value(parser.expect_number(), "info", keyword)?;
             }
             "smooth" => {
-                det.smooth = result_or(
-                    parser.expect_number(),
-                    format!("Unable to parse info.{} value", keyword),
-                )?;
+                det.smooth = value(parser.expect_number(), "info", keyword)?;
             }
             "aa" => {
-                det.aa = result_or(
-                    parser.expect_number(),
-                    format!("Unable to parse info.{} value", keyword),
-                )?;
+                det.aa = value(parser.expect_number(), "info", keyword)?;
             }
             "padding" => {
                 let mut list = [0; 4];
-                for i in 0..4 {
+                for (i, item) in list.iter_mut().enumerate() {
                     if i > 0 {
-                        result_or(parser.expect(",").get(), "Comma missing from padding")?;
+                        expect_or(parser.expect(",").get(), "Comma missing from padding")?;
                     }
-                    list[i] = result_or(
-                        parser.expect_number(),
-                        format!("Unable to parse info.{} value", keyword),
-                    )?;
+                    *item = value(parser.expect_number(), "info", keyword)?;
                 }
                 det.padding = list;
             }
             "spacing" => {
                 let mut list = [0; 2];
-                for i in 0..2 {
+                for (i, item) in list.iter_mut().enumerate() {
                     if i > 0 {
-                        result_or(parser.expect(",").get(), "Comma missing from padding")?;
+                        expect_or(parser.expect(",").get(), "Comma missing from padding")?;
                     }
-                    list[i] = result_or(
-                        parser.expect_number(),
-                        format!("Unable to parse info.{} value", keyword),
-                    )?;
+                    *item = value(parser.expect_number(), "info", keyword)?;
                 }
                 det.spacing = list;
             }
             "outline" => {
-                det.italic = result_or(
-                    parser.expect_number(),
-                    format!("Unable to parse info.{} value", keyword),
-                )?;
+                det.outline = value(parser.expect_number(), "info", keyword)?;
             }
-            _ => return err("Found value that should not exist in an info-block"),
+            _ => unknown_key(
+                &start,
+                parser,
+                options,
+                "info",
+                keyword,
+                Some(&mut det.extra),
+            )?,
         };
         parser.skip_whitespace();
+        start = parser.clone();
     }
 
     parser.skip_whitespace();
-    result_or(parser.expect("common").get(), "Could not parse \"common\"")?;
+    expect_or(parser.expect("common").get(), "Could not parse \"common\"")?;
 
     parser.skip_whitespace();
-    let mut keyword_res;
-    while {
-        keyword_res = parser
-            .expect("lineHeight")
-            .or("base", &mut parser)
-            .or("scaleW", &mut parser)
-            .or("scaleH", &mut parser)
-            .or("pages", &mut parser)
-            .or("packed", &mut parser)
-            .or("alphaChnl", &mut parser)
-            .or("redChnl", &mut parser)
-            .or("greenChnl", &mut parser)
-            .or("blueChnl", &mut parser)
-            .get();
-        keyword_res.is_ok()
-    } {
-        let keyword = keyword_res.unwrap();
-        result_or(
-            parser.expect("=").get(),
-            format!("Unable to get \"=\" after common.{}", keyword),
-        )?;
-        match &*keyword {
+    let mut start = parser.clone();
+    while let Ok(keyword) = parser.expect_key() {
+        match keyword {
             "lineHeight" => {
-                line_height = result_or(
-                    parser.expect_number(),
-                    format!("Unable to parse common.{} value", keyword),
-                )?;
+                line_height = value(parser.expect_number(), "common", keyword)?;
             }
             "base" => {
-                com.base = result_or(
-                    parser.expect_number(),
-                    format!("Unable to parse common.{} value", keyword),
-                )?;
+                com.base = value(parser.expect_number(), "common", keyword)?;
             }
             "scaleW" => {
-                com.scale_w = result_or(
-                    parser.expect_number(),
-                    format!("Unable to parse common.{} value", keyword),
-                )?;
+                com.scale_w = value(parser.expect_number(), "common", keyword)?;
             }
             "scaleH" => {
-                com.scale_h = result_or(
-                    parser.expect_number(),
-                    format!("Unable to parse common.{} value", keyword),
-                )?;
+                com.scale_h = value(parser.expect_number(), "common", keyword)?;
             }
             "pages" => {
-                com.pages_count = result_or(
-                    parser.expect_number(),
-                    format!("Unable to parse common.{} value", keyword),
-                )?;
+                com.pages_count = value(parser.expect_number(), "common", keyword)?;
             }
             "packed" => {
-                com.packed = result_or(
-                    parser.expect_number(),
-                    format!("Unable to parse common.{} value", keyword),
-                )?;
+                com.packed = value(parser.expect_number(), "common", keyword)?;
             }
             "alphaChnl" => {
-                com.alpha_channel = result_or(
-                    parser.expect_number(),
-                    format!("Unable to parse common.{} value", keyword),
-                )?;
+                com.alpha_channel = value(parser.expect_number(), "common", keyword)?;
             }
             "redChnl" => {
-                com.red_channel = result_or(
-                    parser.expect_number(),
-                    format!("Unable to parse common.{} value", keyword),
-                )?;
+                com.red_channel = value(parser.expect_number(), "common", keyword)?;
             }
             "greenChnl" => {
-                com.green_channel = result_or(
-                    parser.expect_number(),
-                    format!("Unable to parse common.{} value", keyword),
-                )?;
+                com.green_channel = value(parser.expect_number(), "common", keyword)?;
             }
             "blueChnl" => {
-                com.blue_channel = result_or(
-                    parser.expect_number(),
-                    format!("Unable to parse common.{} value", keyword),
-                )?;
+                com.blue_channel = value(parser.expect_number(), "common", keyword)?;
             }
-            _ => return err("Found value that should not exist in an common-block"),
+            _ => unknown_key(
+                &start,
+                parser,
+                options,
+                "common",
+                keyword,
+                Some(&mut com.extra),
+            )?,
         };
         parser.skip_whitespace();
+        start = parser.clone();
     }
 
     parser.skip_whitespace();
     let mut pages = Vec::new();
     while parser.expect("page").get().is_ok() {
-        let mut page = Page {
+        let mut page = PageRef {
             id: 0,
-            image_path: PathBuf::new(),
+            image_path: "",
+            extra: BTreeMap::new(),
         };
         parser.skip_whitespace();
-        let mut keyword_res;
-        while {
-            keyword_res = parser.expect("id").or("file", &mut parser).get();
-            keyword_res.is_ok()
-        } {
-            let keyword = keyword_res.unwrap();
-            result_or(
-                parser.expect("=").get(),
-                format!("Unable to get \"=\" after page.{}", keyword),
-            )?;
-            match &*keyword {
+        let mut start = parser.clone();
+        while let Ok(keyword) = parser.expect_key() {
+            match keyword {
                 "id" => {
-                    page.id = result_or(
-                        parser.expect_number(),
-                        format!("Unable to parse page.{} value", keyword),
-                    )?;
+                    page.id = value(parser.expect_number(), "page", keyword)?;
                 }
                 "file" => {
-                    let image_name = result_or(
-                        parser.expect_ident(),
-                        format!("Unable to parse page.{} value", keyword),
-                    )?;
-                    page.image_path.push(image_name);
+                    page.image_path = value(parser.expect_ident(), "page", keyword)?;
                 }
-                _ => return err("Found value that should not exist in an page-block"),
+                _ => unknown_key(
+                    &start,
+                    parser,
+                    options,
+                    "page",
+                    keyword,
+                    Some(&mut page.extra),
+                )?,
             };
             parser.skip_whitespace();
+            start = parser.clone();
         }
         pages.push(page);
         parser.skip_whitespace();
@@ -291,134 +434,137 @@ pub(crate) fn load<T: Into<String>>(sfl_contents: T) -> Result<BMFont, Error> {
 
     parser.skip_whitespace();
     if pages.is_empty() {
-        return err("Could not find any pages!");
+        return semantic_err("Could not find any pages!");
     }
 
-    parser.skip_whitespace();
-    if parser.expect("chars count=").get().is_ok() {
-        result_or(parser.expect_number::<u32>(), "Unable to parse chars count")?;
+    Ok(BMFontRef {
+        font_name,
+        size,
+        info_details: Some(det),
+        line_height,
+        common_details: Some(com),
+        pages,
+        chars: HashMap::new(),
+        kernings: HashMap::new(),
+        declared_counts: DeclaredCounts::default(),
+    })
+}
+
+/// Parses the value of a `chars count=` or `kernings count=` line.
+fn count(parser: &mut Parser, block: &str) -> Result<usize, BMFontError> {
+    expect_or(
+        parser.expect_number(),
+        format!("Unable to parse {} count", block),
+    )
+}
+
+/// Warns if the declared count of a block, if any, does not match the amount found.
+fn check_count(
+    block: &str,
+    declared: Option<(usize, Position)>,
+    found: usize,
+    options: &mut ParseOptions,
+) {
+    if let Some((count, position)) = declared.filter(|&(count, _)| count != found) {
+        options.warn(
+            format!(
+                "{} count is {}, but {} {} were found",
+                block, count, found, block
+            ),
+            Some(position),
+        );
     }
+}
 
-    let mut chars = HashMap::new();
+/// Parses the keys of a char line, after its tag.
+fn char_keys(parser: &mut Parser, options: &mut ParseOptions) -> Result<BMCharacter, BMFontError> {
+    let mut c = BMCharacter {
+        id: 0,
+        x: 0,
+        y: 0,
+        width: 0,
+        height: 0,
+        xoffset: 0,
+        yoffset: 0,
+        xadvance: 0,
+        page: 0,
+        channel: 15,
+        extra: BTreeMap::new(),
+    };
 
     parser.skip_whitespace();
-    while parser.expect("char").get().is_ok() {
-        let mut c = BMCharacter {
-            id: 0,
-            x: 0,
-            y: 0,
-            width: 0,
-            height: 0,
-            xoffset: 0,
-            yoffset: 0,
-            xadvance: 0,
-            page: 0,
-            channel: 15,
-        };
-
-        parser.skip_whitespace();
-        let mut keyword_res;
-        while {
-            keyword_res = parser
-                .expect("id")
-                .or("xoffset", &mut parser)
-                .or("yoffset", &mut parser)
-                .or("xadvance", &mut parser)
-                .or("width", &mut parser)
-                .or("height", &mut parser)
-                .or("x", &mut parser)
-                .or("y", &mut parser)
-                .or("page", &mut parser)
-                .or("chnl", &mut parser)
-                .get();
-            keyword_res.is_ok()
-        } {
-            let keyword = keyword_res.unwrap();
-            result_or(
-                parser.expect("=").get(),
-                format!("Unable to get \"=\" after char.{}", keyword),
-            )?;
-            match &*keyword {
-                "id" => {
-                    c.id = result_or(
-                        parser.expect_number(),
-                        format!("Unable to parse char.{} value", keyword),
-                    )?;
-                }
-                "x" => {
-                    c.x = result_or(
-                        parser.expect_number(),
-                        format!("Unable to parse char.{} value", keyword),
-                    )?;
-                }
-                "y" => {
-                    c.y = result_or(
-                        parser.expect_number(),
-                        format!("Unable to parse char.{} value", keyword),
-                    )?;
-                }
-                "width" => {
-                    c.width = result_or(
-                        parser.expect_number(),
-                        format!("Unable to parse char.{} value", keyword),
-                    )?;
-                }
-                "height" => {
-                    c.height = result_or(
-                        parser.expect_number(),
-                        format!("Unable to parse char.{} value", keyword),
-                    )?;
-                }
-                "xoffset" => {
-                    c.xoffset = result_or(
-                        parser.expect_number(),
-                        format!("Unable to parse char.{} value", keyword),
-                    )?;
-                }
-                "yoffset" => {
-                    c.yoffset = result_or(
-                        parser.expect_number(),
-                        format!("Unable to parse char.{} value", keyword),
-                    )?;
-                }
-                "xadvance" => {
-                    c.xadvance = result_or(
-                        parser.expect_number(),
-                        format!("Unable to parse char.{} value", keyword),
-                    )?;
-                }
-                "page" => {
-                    c.page = result_or(
-                        parser.expect_number(),
-                        format!("Unable to parse char.{} value", keyword),
-                    )?;
-                }
-                "chnl" => {
-                    c.channel = result_or(
-                        parser.expect_number(),
-                        format!("Unable to parse char.{} value", keyword),
-                    )?;
-                }
-                _ => return err("Found value that should not exist in an char-block"),
-            }
-            parser.skip_whitespace();
+    let mut start = parser.clone();
+    while let Ok(keyword) = parser.expect_key() {
+        match keyword {
+            "id" => c.id = value(parser.expect_number::<CharId>(), "char", keyword)?.0,
+            "x" => c.x = value(parser.expect_number(), "char", keyword)?,
+            "y" => c.y = value(parser.expect_number(), "char", keyword)?,
+            "width" => c.width = value(parser.expect_number(), "char", keyword)?,
+            "height" => c.height = value(parser.expect_number(), "char", keyword)?,
+            "xoffset" => c.xoffset = value(parser.expect_number(), "char", keyword)?,
+            "yoffset" => c.yoffset = value(parser.expect_number(), "char", keyword)?,
+            "xadvance" => c.xadvance = value(parser.expect_number(), "char", keyword)?,
+            "page" => c.page = value(parser.expect_number(), "char", keyword)?,
+            "chnl" => c.channel = value(parser.expect_number(), "char", keyword)?,
+            _ => unknown_key(&start, parser, options, "char", keyword, Some(&mut c.extra))?,
         }
-        chars.insert(c.id, c);
         parser.skip_whitespace();
+        start = parser.clone();
     }
+    Ok(c)
+}
+
+/// Parses the keys of a kerning line, after its tag.
+fn kerning_keys(
+    parser: &mut Parser,
+    options: &mut ParseOptions,
+) -> Result<((u32, u32), i32), BMFontError> {
+    let mut first = 0;
+    let mut second = 0;
+    let mut amount = 0;
 
     parser.skip_whitespace();
-    if !parser.is_finished() {
-        err("Something found after chars, should not")
-    } else {
-        Ok(BMFont {
-            font_name,
-            size,
-            info_details: Some(det),
-            line_height,
-            common_details: Some(com),
-            pages: pages,
-            chars,
-        })
+    let mut start = parser.clone();
+    while let Ok(keyword) = parser.expect_key() {
+        match keyword {
+            "first" => first = value(parser.expect_number(), "kerning", keyword)?,
+            "second" => second = value(parser.expect_number(), "kerning", keyword)?,
+            "amount" => amount = value(parser.expect_number(), "kerning", keyword)?,
+            _ => unknown_key(&start, parser, options, "kerning", keyword, None)?,
+        }
+        parser.skip_whitespace();
+        start = parser.clone();
     }
+    Ok(((first, second), amount))
+}
+
+/// Handles a key that is not known in `block`: an error in strict mode, otherwise its value is
+/// skipped with a warning and kept in `extra`.
+fn unknown_key(
+    start: &Parser,
+    parser: &mut Parser,
+    options: &mut ParseOptions,
+    block: &str,
+    key: &str,
+    extra: Option<&mut BTreeMap<String, String>>,
+) -> Result<(), BMFontError> {
+    if options.strict {
+        return start.error(format!("Unknown key {}.{}", block, key));
+    }
+    let value = value(parser.expect_ident(), block, key)?;
+    options.warn(
+        format!("Skipping unknown key {}.{}", block, key),
+        Some(start.position()),
+    );
+    if let Some(extra) = extra {
+        extra.insert(key.to_owned(), value.to_owned());
+    }
+    Ok(())
+}
+
+/// Returns the parsed value of `block.key`, only building the error message if it failed.
+fn value<T>(res: Result<T, Unexpected>, block: &str, key: &str) -> Result<T, BMFontError> {
+    res.map_err(|unexpected| {
+        unexpected.into_error(format!("Unable to parse {}.{} value", block, key))
+    })
 }
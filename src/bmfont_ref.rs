@@ -0,0 +1,217 @@
+use crate::{bmfont_parser, sfl_parser, unsupported_err, BMFont, BMFontError, ParseOptions};
+use crate::{BMCharacter, CommonDetails, DeclaredCounts, Format, InfoDetails, Page};
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+/// A font that borrows its names from the text it was parsed from, so that parsing it does not
+/// copy any strings. Only the text .fnt and .sfl formats can be parsed this way.
+///
+/// Turn it into a [`BMFont`][bmfont] with [`to_owned`][to_owned].
+///
+/// # Examples
+/// ```
+/// use bmfont_parser::{BMFontRef, Format};
+///
+/// static IOSEVKA_FNT: &str = include_str!("../examples/fonts/iosevka.fnt");
+///
+/// let font = BMFontRef::parse(&Format::BMFont, IOSEVKA_FNT).unwrap();
+/// assert_eq!(font.font_name, "Iosevka");
+/// assert_eq!(font.pages[0].image_path, "iosevka.png");
+///
+/// let owned = font.to_owned();
+/// assert_eq!(owned.font_name, "Iosevka");
+/// ```
+///
+/// [bmfont]: struct.BMFont.html
+/// [to_owned]: #method.to_owned
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub struct BMFontRef<'a> {
+    /// The name of the font.
+    pub font_name: &'a str,
+    /// Size of the font.
+    pub size: u32,
+    /// Some details from the Info-block, not available in .sfl files
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub info_details: Option<InfoDetailsRef<'a>>,
+    /// Line height of the font.
+    pub line_height: u32,
+    /// Some details from the Common-block, not available in .sfl files
+    pub common_details: Option<CommonDetails>,
+    /// The pages of this font
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub pages: Vec<PageRef<'a>>,
+    /// Hashmap of the characters in the font. <CharID, [`BMCharacter`][bmcharacter]>
+    ///
+    /// [bmcharacter]: struct.BMCharacter.html
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::chars"))]
+    pub chars: HashMap<u32, BMCharacter>,
+    /// Hashmap of the kerning pairs in the font. <(first CharID, second CharID), amount>
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::kernings"))]
+    pub kernings: HashMap<(u32, u32), i32>,
+    /// The counts declared in the font file, which may not match what it contains.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub declared_counts: DeclaredCounts,
+}
+
+/// Some details from the info block, borrowing the charset name. See
+/// [`InfoDetails`][info_details].
+///
+/// [info_details]: struct.InfoDetails.html
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub struct InfoDetailsRef<'a> {
+    /// Is the font bold
+    pub bold: u32,
+    /// Is the font italic
+    pub italic: u32,
+    /// OEM charset name
+    pub charset: &'a str,
+    /// Is the font unicode
+    pub unicode: u32,
+    /// height-stretch of the font
+    pub stretch_h: u32,
+    /// 1 if smoothing was turned on
+    pub smooth: u32,
+    /// Supersampling level used. 1 means no supersampling
+    pub aa: u32,
+    /// Padding for each character [up, right, down, left]
+    pub padding: [u32; 4],
+    /// Spacing for each character [horizontal, vertical]
+    pub spacing: [u32; 2],
+    /// Outline thickness
+    pub outline: u32,
+    /// Keys of the info block that are not known, kept when parsing leniently
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub extra: BTreeMap<String, String>,
+}
+
+/// A page of the font, borrowing the file name of its image. See [`Page`][page].
+///
+/// [page]: struct.Page.html
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub struct PageRef<'a> {
+    /// The id of this page
+    pub id: u32,
+    /// The file name of the image, as written in the font
+    pub image_path: &'a str,
+    /// Keys of the page block that are not known, kept when parsing leniently
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub extra: BTreeMap<String, String>,
+}
+
+impl<'a> BMFontRef<'a> {
+    /// Parse a `BMFontRef` from the given text, which should be the contents of a font file in
+    /// the given `format`. Returns an `Unsupported` error for formats other than
+    /// [`Format::SFL`][sfl] and [`Format::BMFont`][bmfont].
+    ///
+    /// Page image paths are kept as they are written in the font.
+    ///
+    /// [sfl]: enum.Format.html#variant.SFL
+    /// [bmfont]: enum.Format.html#variant.BMFont
+    pub fn parse(format: &Format, contents: &'a str) -> Result<BMFontRef<'a>, BMFontError> {
+        BMFontRef::parse_with(format, contents, &mut ParseOptions::default())
+    }
+
+    /// Like [`parse`][parse], but with the given [`ParseOptions`][options].
+    ///
+    /// [parse]: #method.parse
+    /// [options]: struct.ParseOptions.html
+    pub fn parse_with(
+        format: &Format,
+        contents: &'a str,
+        options: &mut ParseOptions,
+    ) -> Result<BMFontRef<'a>, BMFontError> {
+        let font = match format {
+            Format::SFL => sfl_parser::load(contents, options)?,
+            Format::BMFont => bmfont_parser::load(contents, options)?,
+            _ => {
+                return unsupported_err(format!(
+                    "{:?} fonts can not be parsed without copying, only text .fnt and .sfl fonts",
+                    format
+                ))
+            }
+        };
+        BMFont::report_parsed(
+            format,
+            font.common_details.as_ref(),
+            font.pages.len(),
+            font.chars.len(),
+            font.kernings.len(),
+            options,
+        );
+        Ok(font)
+    }
+
+    /// Returns an owned [`BMFont`][bmfont] with copies of the borrowed names.
+    ///
+    /// [bmfont]: struct.BMFont.html
+    pub fn to_owned(&self) -> BMFont {
+        BMFont::from(self.clone())
+    }
+}
+
+impl From<BMFontRef<'_>> for BMFont {
+    fn from(font: BMFontRef) -> BMFont {
+        BMFont {
+            font_name: font.font_name.to_owned(),
+            size: font.size,
+            info_details: font.info_details.map(InfoDetails::from),
+            line_height: font.line_height,
+            common_details: font.common_details,
+            distance_field: None,
+            pages: font.pages.into_iter().map(Page::from).collect(),
+            chars: font.chars,
+            kernings: font.kernings,
+            declared_counts: font.declared_counts,
+        }
+    }
+}
+
+impl From<InfoDetailsRef<'_>> for InfoDetails {
+    fn from(det: InfoDetailsRef) -> InfoDetails {
+        InfoDetails {
+            bold: det.bold,
+            italic: det.italic,
+            charset: det.charset.to_owned(),
+            unicode: det.unicode,
+            stretch_h: det.stretch_h,
+            smooth: det.smooth,
+            aa: det.aa,
+            padding: det.padding,
+            spacing: det.spacing,
+            outline: det.outline,
+            extra: det.extra,
+        }
+    }
+}
+
+impl From<PageRef<'_>> for Page {
+    fn from(page: PageRef) -> Page {
+        Page {
+            id: page.id,
+            image_path: PathBuf::from(page.image_path),
+            extra: page.extra,
+        }
+    }
+}
+
+impl Display for BMFontRef<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "BMFontRef: {{ name: {}, line_height: {}, size: {}, amount of characters: {}, pages: {:?} }}",
+            self.font_name,
+            self.line_height,
+            self.size,
+            self.chars.len(),
+            self.pages
+        )
+    }
+}
@@ -0,0 +1,240 @@
+use crate::{
+    unsupported_err, BMCharacter, BMFont, BMFontError, CharId, CommonDetails, InfoDetails, Page,
+};
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+
+pub(crate) fn write<W: Write>(bmfont: &BMFont, mut w: W) -> Result<(), BMFontError> {
+    write_line(&mut w, "info", &info_pairs(bmfont))?;
+    write_line(&mut w, "common", &common_pairs(bmfont))?;
+    for page in bmfont.pages.iter() {
+        write_line(&mut w, "page", &page_pairs(page))?;
+    }
+
+    write_line(
+        &mut w,
+        "chars",
+        &[Pair::number("count", bmfont.chars.len())],
+    )?;
+    let mut chars: Vec<_> = bmfont.chars.values().collect();
+    chars.sort_by_key(|c| c.id);
+    for c in chars {
+        write_line(&mut w, "char", &char_pairs(c))?;
+    }
+
+    if !bmfont.kernings.is_empty() {
+        write_line(
+            &mut w,
+            "kernings",
+            &[Pair::number("count", bmfont.kernings.len())],
+        )?;
+        let mut kernings: Vec<_> = bmfont.kernings.iter().collect();
+        kernings.sort();
+        for (&(first, second), &amount) in kernings {
+            write_line(&mut w, "kerning", &kerning_pairs(first, second, amount))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_line<W: Write>(w: &mut W, tag: &str, pairs: &[Pair]) -> Result<(), BMFontError> {
+    if let Some(pair) = pairs.iter().find(|pair| pair.value.contains('"')) {
+        return unsupported_err(format!(
+            "Unable to write {} {} {:?} into a .fnt file, which has no way to escape quotes",
+            tag, pair.key, pair.value
+        ));
+    }
+    write!(w, "{}", tag)?;
+    for pair in pairs {
+        write!(w, " {}", pair)?;
+    }
+    writeln!(w)?;
+    Ok(())
+}
+
+/// A `key=value` pair of the text format.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Pair {
+    pub key: String,
+    pub value: String,
+    pub quoted: bool,
+}
+
+impl Pair {
+    fn number<T: Display>(key: &str, value: T) -> Pair {
+        Pair {
+            key: key.to_owned(),
+            value: value.to_string(),
+            quoted: false,
+        }
+    }
+
+    fn text(key: &str, value: &str) -> Pair {
+        Pair {
+            key: key.to_owned(),
+            value: value.to_owned(),
+            quoted: true,
+        }
+    }
+
+    /// A pair from an `extra` map, only quoted if the parser needs it to be.
+    fn extra(key: &str, value: &str) -> Pair {
+        Pair {
+            key: key.to_owned(),
+            value: value.to_owned(),
+            quoted: value.is_empty() || value.contains(char::is_whitespace),
+        }
+    }
+}
+
+impl Display for Pair {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        if self.quoted {
+            write!(f, "{}=\"{}\"", self.key, self.value)
+        } else {
+            write!(f, "{}={}", self.key, self.value)
+        }
+    }
+}
+
+/// The pairs of the info line, followed by its `extra` keys.
+pub(crate) fn info_pairs(bmfont: &BMFont) -> Vec<Pair> {
+    let det = info_details(bmfont);
+    let mut pairs = vec![
+        Pair::text("face", &bmfont.font_name),
+        Pair::number("size", bmfont.size),
+        Pair::number("bold", det.bold),
+        Pair::number("italic", det.italic),
+        Pair::text("charset", &det.charset),
+        Pair::number("unicode", det.unicode),
+        Pair::number("stretchH", det.stretch_h),
+        Pair::number("smooth", det.smooth),
+        Pair::number("aa", det.aa),
+        Pair::number("padding", list(&det.padding)),
+        Pair::number("spacing", list(&det.spacing)),
+        Pair::number("outline", det.outline),
+    ];
+    pairs.extend(extra_pairs(&det.extra));
+    pairs
+}
+
+/// The pairs of the common line, followed by its `extra` keys.
+pub(crate) fn common_pairs(bmfont: &BMFont) -> Vec<Pair> {
+    let com = common_details(bmfont);
+    let mut pairs = vec![
+        Pair::number("lineHeight", bmfont.line_height),
+        Pair::number("base", com.base),
+        Pair::number("scaleW", com.scale_w),
+        Pair::number("scaleH", com.scale_h),
+        Pair::number("pages", com.pages_count),
+        Pair::number("packed", com.packed),
+        Pair::number("alphaChnl", com.alpha_channel),
+        Pair::number("redChnl", com.red_channel),
+        Pair::number("greenChnl", com.green_channel),
+        Pair::number("blueChnl", com.blue_channel),
+    ];
+    pairs.extend(extra_pairs(&com.extra));
+    pairs
+}
+
+/// The pairs of a page line, followed by its `extra` keys.
+pub(crate) fn page_pairs(page: &Page) -> Vec<Pair> {
+    let mut pairs = vec![
+        Pair::number("id", page.id),
+        Pair::text("file", &page.image_path.to_string_lossy()),
+    ];
+    pairs.extend(extra_pairs(&page.extra));
+    pairs
+}
+
+/// The pairs of a char line, followed by its `extra` keys.
+pub(crate) fn char_pairs(c: &BMCharacter) -> Vec<Pair> {
+    let mut pairs = vec![
+        Pair::number("id", CharId(c.id)),
+        Pair::number("x", c.x),
+        Pair::number("y", c.y),
+        Pair::number("width", c.width),
+        Pair::number("height", c.height),
+        Pair::number("xoffset", c.xoffset),
+        Pair::number("yoffset", c.yoffset),
+        Pair::number("xadvance", c.xadvance),
+        Pair::number("page", c.page),
+        Pair::number("chnl", c.channel),
+    ];
+    pairs.extend(extra_pairs(&c.extra));
+    pairs
+}
+
+pub(crate) fn kerning_pairs(first: u32, second: u32, amount: i32) -> Vec<Pair> {
+    vec![
+        Pair::number("first", first),
+        Pair::number("second", second),
+        Pair::number("amount", amount),
+    ]
+}
+
+fn extra_pairs(extra: &BTreeMap<String, String>) -> Vec<Pair> {
+    extra
+        .iter()
+        .map(|(key, value)| Pair::extra(key, value))
+        .collect()
+}
+
+fn list(values: &[u32]) -> String {
+    let values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+    values.join(",")
+}
+
+/// Returns the info details of the font, or sensible defaults if they are not available.
+pub(crate) fn info_details(bmfont: &BMFont) -> InfoDetails {
+    match &bmfont.info_details {
+        Some(det) => det.clone(),
+        None => InfoDetails {
+            bold: 0,
+            italic: 0,
+            charset: String::new(),
+            unicode: 1,
+            stretch_h: 100,
+            smooth: 1,
+            aa: 1,
+            padding: [0; 4],
+            spacing: [0; 2],
+            outline: 0,
+            extra: BTreeMap::new(),
+        },
+    }
+}
+
+/// Returns the common details of the font, or defaults derived from the rest of the font if
+/// they are not available: the base is placed at the line height, and the texture size is the
+/// smallest one containing all characters.
+pub(crate) fn common_details(bmfont: &BMFont) -> CommonDetails {
+    match &bmfont.common_details {
+        Some(com) => com.clone(),
+        None => CommonDetails {
+            base: bmfont.line_height,
+            scale_w: bmfont
+                .chars
+                .values()
+                .map(|c| c.x + c.width)
+                .max()
+                .unwrap_or(0),
+            scale_h: bmfont
+                .chars
+                .values()
+                .map(|c| c.y + c.height)
+                .max()
+                .unwrap_or(0),
+            pages_count: bmfont.pages.len() as u32,
+            packed: 0,
+            alpha_channel: 0,
+            red_channel: 0,
+            green_channel: 0,
+            blue_channel: 0,
+            extra: BTreeMap::new(),
+        },
+    }
+}
@@ -0,0 +1,191 @@
+use crate::bmfont_parser::{CHAR_KEYS, COMMON_KEYS, INFO_KEYS, KERNING_KEYS, PAGE_KEYS};
+use crate::xml::{self, Element};
+use crate::{result_or, semantic_err, syntax_err, BMFontError, CharId, ParseOptions};
+use crate::{BMCharacter, BMFont, CommonDetails, DeclaredCounts, InfoDetails, Page};
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+pub(crate) fn load(xml_contents: &str, options: &mut ParseOptions) -> Result<BMFont, BMFontError> {
+    let font = xml::parse(xml_contents)?;
+    if font.name != "font" {
+        return syntax_err("Could not find the <font> element");
+    }
+
+    let info = match font.child("info") {
+        Some(info) => info,
+        None => return syntax_err("Could not find the <info> element"),
+    };
+    let font_name = info.attribute("face").unwrap_or_default().to_owned();
+    let size = number(info, "size", 0)?;
+    let det = InfoDetails {
+        bold: number(info, "bold", 0)?,
+        italic: number(info, "italic", 0)?,
+        charset: info.attribute("charset").unwrap_or_default().to_owned(),
+        unicode: number(info, "unicode", 0)?,
+        stretch_h: number(info, "stretchH", 0)?,
+        smooth: number(info, "smooth", 0)?,
+        aa: number(info, "aa", 0)?,
+        padding: list(info, "padding")?,
+        spacing: list(info, "spacing")?,
+        outline: number(info, "outline", 0)?,
+        extra: extra(info, INFO_KEYS, options)?,
+    };
+
+    let common = match font.child("common") {
+        Some(common) => common,
+        None => return syntax_err("Could not find the <common> element"),
+    };
+    let line_height = number(common, "lineHeight", 0)?;
+    let com = CommonDetails {
+        base: number(common, "base", 0)?,
+        scale_w: number(common, "scaleW", 0)?,
+        scale_h: number(common, "scaleH", 0)?,
+        pages_count: number(common, "pages", 0)?,
+        packed: number(common, "packed", 0)?,
+        alpha_channel: number(common, "alphaChnl", 0)?,
+        red_channel: number(common, "redChnl", 0)?,
+        green_channel: number(common, "greenChnl", 0)?,
+        blue_channel: number(common, "blueChnl", 0)?,
+        extra: extra(common, COMMON_KEYS, options)?,
+    };
+
+    let mut pages = Vec::new();
+    for page in font.children("pages").flat_map(|p| p.children("page")) {
+        pages.push(Page {
+            id: number(page, "id", 0)?,
+            image_path: PathBuf::from(page.attribute("file").unwrap_or_default()),
+            extra: extra(page, PAGE_KEYS, options)?,
+        });
+    }
+    if pages.is_empty() {
+        return semantic_err("Could not find any pages!");
+    }
+
+    let mut chars = HashMap::new();
+    let mut declared_counts = DeclaredCounts::default();
+    for c in font.children("chars").flat_map(|c| c.children("char")) {
+        let c = BMCharacter {
+            id: number(c, "id", CharId(0))?.0,
+            x: number(c, "x", 0)?,
+            y: number(c, "y", 0)?,
+            width: number(c, "width", 0)?,
+            height: number(c, "height", 0)?,
+            xoffset: number(c, "xoffset", 0)?,
+            yoffset: number(c, "yoffset", 0)?,
+            xadvance: number(c, "xadvance", 0)?,
+            page: number(c, "page", 0)?,
+            channel: number(c, "chnl", 15)?,
+            extra: extra(c, CHAR_KEYS, options)?,
+        };
+        if let Some(old) = chars.insert(c.id, c) {
+            declared_counts.duplicate_char(old.id, None, options);
+        }
+    }
+    for declared in font.children("chars") {
+        let count: usize = number(declared, "count", chars.len())?;
+        if declared.attribute("count").is_some() {
+            declared_counts.chars = Some(count);
+        }
+        if count != chars.len() {
+            options.warn(
+                format!(
+                    "chars count is {}, but {} chars were found",
+                    count,
+                    chars.len()
+                ),
+                None,
+            );
+        }
+    }
+
+    let mut kernings = HashMap::new();
+    for declared in font.children("kernings") {
+        if declared.attribute("count").is_some() {
+            declared_counts.kernings = Some(number(declared, "count", 0)?);
+        }
+    }
+    for kerning in font
+        .children("kernings")
+        .flat_map(|k| k.children("kerning"))
+    {
+        extra(kerning, KERNING_KEYS, options)?;
+        kernings.insert(
+            (number(kerning, "first", 0)?, number(kerning, "second", 0)?),
+            number(kerning, "amount", 0)?,
+        );
+    }
+
+    Ok(BMFont {
+        font_name,
+        size,
+        info_details: Some(det),
+        line_height,
+        common_details: Some(com),
+        distance_field: None,
+        pages,
+        chars,
+        kernings,
+        declared_counts,
+    })
+}
+
+/// Parses the attribute `name` of `element` as a number, or returns `default` if it is missing.
+fn number<T: FromStr>(element: &Element, name: &str, default: T) -> Result<T, BMFontError> {
+    match element.attribute(name) {
+        Some(value) => result_or(
+            value.trim().parse(),
+            format!("Unable to parse {}.{} value", element.name, name),
+        ),
+        None => Ok(default),
+    }
+}
+
+/// Parses the attribute `name` of `element` as a comma-separated list of `N` numbers.
+fn list<const N: usize>(element: &Element, name: &str) -> Result<[u32; N], BMFontError> {
+    let mut list = [0; N];
+    if let Some(value) = element.attribute(name) {
+        let values: Vec<&str> = value.split(',').collect();
+        if values.len() != N {
+            return syntax_err(format!(
+                "Expected {} values in {}.{}, found {}",
+                N,
+                element.name,
+                name,
+                values.len()
+            ));
+        }
+        for (item, value) in list.iter_mut().zip(values) {
+            *item = result_or(
+                value.trim().parse(),
+                format!("Unable to parse {}.{} value", element.name, name),
+            )?;
+        }
+    }
+    Ok(list)
+}
+
+/// Returns the attributes of `element` that are not in `known`. They are an error in strict
+/// mode, otherwise a warning is reported for each of them.
+fn extra(
+    element: &Element,
+    known: &[&str],
+    options: &mut ParseOptions,
+) -> Result<BTreeMap<String, String>, BMFontError> {
+    let mut extra = BTreeMap::new();
+    for (key, value) in element.attributes.iter() {
+        if known.contains(&key.as_str()) {
+            continue;
+        }
+        if options.strict {
+            return syntax_err(format!("Unknown key {}.{}", element.name, key));
+        }
+        options.warn(
+            format!("Skipping unknown key {}.{}", element.name, key),
+            None,
+        );
+        extra.insert(key.clone(), value.clone());
+    }
+    Ok(extra)
+}
@@ -0,0 +1,111 @@
+use crate::bmfont_writer::{common_details, info_details};
+use crate::xml::escape;
+use crate::{BMFont, BMFontError, CharId};
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+pub(crate) fn write<W: Write>(bmfont: &BMFont, mut w: W) -> Result<(), BMFontError> {
+    writeln!(w, "<?xml version=\"1.0\"?>")?;
+    writeln!(w, "<font>")?;
+
+    let det = info_details(bmfont);
+    writeln!(
+        w,
+        "  <info face=\"{}\" size=\"{}\" bold=\"{}\" italic=\"{}\" charset=\"{}\" unicode=\"{}\" stretchH=\"{}\" smooth=\"{}\" aa=\"{}\" padding=\"{},{},{},{}\" spacing=\"{},{}\" outline=\"{}\"{}/>",
+        escape(&bmfont.font_name),
+        bmfont.size,
+        det.bold,
+        det.italic,
+        escape(&det.charset),
+        det.unicode,
+        det.stretch_h,
+        det.smooth,
+        det.aa,
+        det.padding[0],
+        det.padding[1],
+        det.padding[2],
+        det.padding[3],
+        det.spacing[0],
+        det.spacing[1],
+        det.outline,
+        extra(&det.extra)
+    )?;
+
+    let com = common_details(bmfont);
+    writeln!(
+        w,
+        "  <common lineHeight=\"{}\" base=\"{}\" scaleW=\"{}\" scaleH=\"{}\" pages=\"{}\" packed=\"{}\" alphaChnl=\"{}\" redChnl=\"{}\" greenChnl=\"{}\" blueChnl=\"{}\"{}/>",
+        bmfont.line_height,
+        com.base,
+        com.scale_w,
+        com.scale_h,
+        com.pages_count,
+        com.packed,
+        com.alpha_channel,
+        com.red_channel,
+        com.green_channel,
+        com.blue_channel,
+        extra(&com.extra)
+    )?;
+
+    writeln!(w, "  <pages>")?;
+    for page in bmfont.pages.iter() {
+        writeln!(
+            w,
+            "    <page id=\"{}\" file=\"{}\"{} />",
+            page.id,
+            escape(&page.image_path.to_string_lossy()),
+            extra(&page.extra)
+        )?;
+    }
+    writeln!(w, "  </pages>")?;
+
+    writeln!(w, "  <chars count=\"{}\">", bmfont.chars.len())?;
+    let mut chars: Vec<_> = bmfont.chars.values().collect();
+    chars.sort_by_key(|c| c.id);
+    for c in chars {
+        writeln!(
+            w,
+            "    <char id=\"{}\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" xoffset=\"{}\" yoffset=\"{}\" xadvance=\"{}\" page=\"{}\" chnl=\"{}\"{} />",
+            CharId(c.id),
+            c.x,
+            c.y,
+            c.width,
+            c.height,
+            c.xoffset,
+            c.yoffset,
+            c.xadvance,
+            c.page,
+            c.channel,
+            extra(&c.extra)
+        )?;
+    }
+    writeln!(w, "  </chars>")?;
+
+    if !bmfont.kernings.is_empty() {
+        writeln!(w, "  <kernings count=\"{}\">", bmfont.kernings.len())?;
+        let mut kernings: Vec<_> = bmfont.kernings.iter().collect();
+        kernings.sort();
+        for ((first, second), amount) in kernings {
+            writeln!(
+                w,
+                "    <kerning first=\"{}\" second=\"{}\" amount=\"{}\" />",
+                first, second, amount
+            )?;
+        }
+        writeln!(w, "  </kernings>")?;
+    }
+
+    writeln!(w, "</font>")?;
+    Ok(())
+}
+
+/// Returns the unknown keys of a block as ` key="value"` attributes.
+fn extra(extra: &BTreeMap<String, String>) -> String {
+    let mut text = String::new();
+    for (key, value) in extra.iter() {
+        text += &format!(" {}=\"{}\"", key, escape(value));
+    }
+    text
+}
@@ -0,0 +1,129 @@
+use crate::Position;
+
+use std::fmt::{Display, Formatter};
+
+/// How important a [`Diagnostic`][diagnostic] is.
+///
+/// [diagnostic]: struct.Diagnostic.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
+pub enum DiagnosticLevel {
+    /// Information about the parsing itself, e.g. how many characters were read.
+    Debug,
+    /// Something in the font looks wrong, but the font could still be loaded.
+    Warning,
+}
+
+/// A warning or debug event reported while parsing a font.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub struct Diagnostic {
+    /// How important this diagnostic is.
+    pub level: DiagnosticLevel,
+    /// Description of what happened.
+    pub message: String,
+    /// Where in the file it happened, if known.
+    pub position: Option<Position>,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{:?}: {}", self.level, self.message)?;
+        if let Some(position) = self.position {
+            write!(f, " at {}", position)?;
+        }
+        Ok(())
+    }
+}
+
+/// Receives the [`Diagnostic`][diagnostic]s reported while parsing a font, e.g. to route them to
+/// a logger.
+///
+/// Implemented for `Vec<Diagnostic>`, which collects them, and for closures taking a
+/// `Diagnostic`.
+///
+/// [diagnostic]: struct.Diagnostic.html
+pub trait DiagnosticSink {
+    /// Called for every reported diagnostic.
+    fn report(&mut self, diagnostic: Diagnostic);
+}
+
+impl DiagnosticSink for Vec<Diagnostic> {
+    fn report(&mut self, diagnostic: Diagnostic) {
+        self.push(diagnostic);
+    }
+}
+
+impl<F: FnMut(Diagnostic)> DiagnosticSink for F {
+    fn report(&mut self, diagnostic: Diagnostic) {
+        self(diagnostic)
+    }
+}
+
+/// Options for loading a font, used by the `*_with` constructors of
+/// [`BMFont`][bmfont].
+///
+/// # Examples
+/// ```
+/// use bmfont_parser::{BMFont, Diagnostic, Format, ParseOptions};
+///
+/// let mut diagnostics: Vec<Diagnostic> = Vec::new();
+/// let mut options = ParseOptions::with_diagnostics(&mut diagnostics);
+///
+/// let bmfont = BMFont::from_path_with(&Format::BMFont, "examples/fonts/iosevka.fnt", &mut options);
+/// assert!(bmfont.is_ok());
+/// ```
+///
+/// [bmfont]: struct.BMFont.html
+pub struct ParseOptions<'a> {
+    /// Receives the warnings and debug events reported while parsing. Nothing is reported if
+    /// this is `None`.
+    pub diagnostics: Option<&'a mut dyn DiagnosticSink>,
+    /// Whether unknown keys in the text, XML and JSON formats are an error. If `false`, they are
+    /// skipped with a warning and kept in the `extra` map of their block. Defaults to `true`.
+    pub strict: bool,
+}
+
+impl<'a> Default for ParseOptions<'a> {
+    fn default() -> ParseOptions<'a> {
+        ParseOptions {
+            diagnostics: None,
+            strict: true,
+        }
+    }
+}
+
+impl<'a> ParseOptions<'a> {
+    /// Returns the default options, reporting diagnostics to `sink`.
+    pub fn with_diagnostics(sink: &'a mut dyn DiagnosticSink) -> ParseOptions<'a> {
+        ParseOptions {
+            diagnostics: Some(sink),
+            ..ParseOptions::default()
+        }
+    }
+
+    pub(crate) fn warn<T: Into<String>>(&mut self, message: T, position: Option<Position>) {
+        self.report(DiagnosticLevel::Warning, message, position);
+    }
+
+    pub(crate) fn debug<T: Into<String>>(&mut self, message: T, position: Option<Position>) {
+        self.report(DiagnosticLevel::Debug, message, position);
+    }
+
+    fn report<T: Into<String>>(
+        &mut self,
+        level: DiagnosticLevel,
+        message: T,
+        position: Option<Position>,
+    ) {
+        if let Some(sink) = &mut self.diagnostics {
+            sink.report(Diagnostic {
+                level,
+                message: message.into(),
+                position,
+            });
+        }
+    }
+}
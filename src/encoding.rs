@@ -0,0 +1,38 @@
+use crate::{ParseOptions, Position};
+
+use std::borrow::Cow;
+
+/// The characters of bytes 0x80 to 0x9F in Windows-1252. The bytes it leaves undefined are
+/// mapped to the control characters of the same value, as in Latin-1.
+const WINDOWS_1252: [char; 32] = [
+    '\u{20AC}', '\u{81}', '\u{201A}', '\u{192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{2C6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8D}', '\u{17D}', '\u{8F}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{2DC}', '\u{2122}', '\u{161}', '\u{203A}', '\u{153}', '\u{9D}', '\u{17E}', '\u{178}',
+];
+
+/// Decodes `bytes` as UTF-8, or as Windows-1252 with a warning if they are not valid UTF-8, as
+/// with fonts written in the system codepage by older versions of BMFont.
+pub(crate) fn decode<'a>(
+    bytes: &'a [u8],
+    position: Option<Position>,
+    options: &mut ParseOptions,
+) -> Cow<'a, str> {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Cow::Borrowed(text),
+        Err(_) => {
+            options.warn(
+                "Text is not valid UTF-8, decoding it as Windows-1252",
+                position,
+            );
+            Cow::Owned(bytes.iter().map(|&b| windows_1252(b)).collect())
+        }
+    }
+}
+
+fn windows_1252(b: u8) -> char {
+    match b {
+        0x80..=0x9F => WINDOWS_1252[usize::from(b - 0x80)],
+        _ => char::from(b),
+    }
+}
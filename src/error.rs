@@ -0,0 +1,94 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+
+/// An error that occurred while loading, parsing or writing a font.
+#[derive(Debug)]
+pub enum BMFontError {
+    /// Reading or writing a file or stream failed, e.g. the font file was not found.
+    Io(io::Error),
+    /// The contents of the font file could not be parsed.
+    Syntax(SyntaxError),
+    /// The font was parsed, but its contents are not valid, e.g. it has no pages or the wrong
+    /// amount of image paths was given for them.
+    Semantic(String),
+    /// The format, or something the font uses, is not supported, e.g. an unknown binary version
+    /// or a font with kernings written into an .sfl file.
+    Unsupported(String),
+}
+
+/// Details of a [`BMFontError::Syntax`][syntax] error.
+///
+/// [syntax]: enum.BMFontError.html#variant.Syntax
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub struct SyntaxError {
+    /// Description of what could not be parsed.
+    pub message: String,
+    /// The tokens that were expected instead of what was found. Empty if not known.
+    pub expected: Vec<String>,
+    /// Where in the file the error occurred, if known.
+    pub position: Option<Position>,
+    /// The line the error occurred on, with a caret under the offending token.
+    pub snippet: Option<String>,
+}
+
+/// A position in a font file. Both the line and the column start at 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub struct Position {
+    /// The line, starting at 1.
+    pub line: usize,
+    /// The column in characters, starting at 1.
+    pub column: usize,
+}
+
+impl Display for BMFontError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            BMFontError::Io(error) => write!(f, "{}", error),
+            BMFontError::Syntax(error) => write!(f, "{}", error),
+            BMFontError::Semantic(message) => write!(f, "{}", message),
+            BMFontError::Unsupported(message) => write!(f, "Unsupported: {}", message),
+        }
+    }
+}
+
+impl Display for SyntaxError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.message)?;
+        if let Some(position) = self.position {
+            write!(f, " at {}", position)?;
+        }
+        if !self.expected.is_empty() {
+            write!(f, ", expected one of {:?}", self.expected)?;
+        }
+        if let Some(snippet) = &self.snippet {
+            write!(f, "\n{}", snippet)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+impl Error for BMFontError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BMFontError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for BMFontError {
+    fn from(error: io::Error) -> BMFontError {
+        BMFontError::Io(error)
+    }
+}
@@ -0,0 +1,443 @@
+//! A text .fnt file that keeps its exact layout, so that edits can be written back without
+//! touching the rest of the file.
+
+use crate::bmfont_parser::{self, CHAR_KEYS, COMMON_KEYS, INFO_KEYS, KERNING_KEYS, PAGE_KEYS};
+use crate::bmfont_writer::{char_pairs, common_pairs, info_pairs, kerning_pairs, page_pairs, Pair};
+use crate::parser::Parser;
+use crate::{expect_or, BMFont, BMFontError, CharId, ParseOptions};
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+use std::io::Write;
+
+/// A text .fnt file that keeps every line as it was written: the order of the lines and keys,
+/// the whitespace, which values are quoted and any unknown keys.
+///
+/// Use [`to_bmfont`][to_bmfont] to get the font, and [`update_from`][update_from] to write the
+/// edits made to it back into the document. Only the lines and keys that changed are touched.
+///
+/// # Examples
+/// ```
+/// use bmfont_parser::FntDocument;
+///
+/// let iosevka_fnt = include_str!("../examples/fonts/iosevka.fnt");
+/// let mut document = FntDocument::parse(iosevka_fnt).unwrap();
+///
+/// let mut bmfont = document.to_bmfont().unwrap();
+/// bmfont.chars.get_mut(&32).unwrap().xadvance += 1;
+/// document.update_from(&bmfont);
+///
+/// let written = document.to_string();
+/// let changed = iosevka_fnt
+///     .lines()
+///     .zip(written.lines())
+///     .filter(|(before, after)| before != after)
+///     .count();
+/// assert_eq!(changed, 1);
+/// ```
+///
+/// [to_bmfont]: #method.to_bmfont
+/// [update_from]: #method.update_from
+#[derive(Debug, Clone, PartialEq)]
+pub struct FntDocument {
+    lines: Vec<Line>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Line {
+    tokens: Vec<Token>,
+    newline: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Space(String),
+    Word(String),
+    Pair(Pair),
+}
+
+impl FntDocument {
+    /// Parses the given text .fnt file, keeping its layout.
+    ///
+    /// Only the shape of the lines is checked here, the values are checked by
+    /// [`to_bmfont`][to_bmfont].
+    ///
+    /// [to_bmfont]: #method.to_bmfont
+    pub fn parse(text: &str) -> Result<FntDocument, BMFontError> {
+        let mut parser = Parser::new(text);
+        let mut lines = Vec::new();
+        let mut tokens = Vec::new();
+        loop {
+            let space = parser.take_while(|c| c == ' ' || c == '\t' || c == '\r');
+            if !space.is_empty() {
+                tokens.push(Token::Space(space.to_owned()));
+            }
+            if parser.is_finished() {
+                if !tokens.is_empty() {
+                    lines.push(Line {
+                        tokens,
+                        newline: false,
+                    });
+                }
+                break;
+            }
+            if parser.expect("\n").get().is_ok() {
+                lines.push(Line {
+                    tokens: std::mem::take(&mut tokens),
+                    newline: true,
+                });
+            } else if let Ok(key) = parser.expect_key() {
+                let quoted = parser.expect("\"").get().is_ok();
+                let value = if quoted {
+                    let value = parser.take_while(|c| c != '"' && c != '\n');
+                    expect_or(
+                        parser.expect("\"").get(),
+                        format!("Unterminated value of {}", key),
+                    )?;
+                    value
+                } else {
+                    parser.take_while(|c| !Parser::is_whitespace(c))
+                };
+                tokens.push(Token::Pair(Pair {
+                    key: key.to_owned(),
+                    value: value.to_owned(),
+                    quoted,
+                }));
+            } else {
+                tokens.push(Token::Word(
+                    parser.take_while(|c| !Parser::is_whitespace(c)).to_owned(),
+                ));
+            }
+        }
+        Ok(FntDocument { lines })
+    }
+
+    /// Returns the document of the given font, laid out like [`BMFont::write_fnt`][write_fnt]
+    /// does.
+    ///
+    /// [write_fnt]: struct.BMFont.html#method.write_fnt
+    pub fn from_bmfont(bmfont: &BMFont) -> Result<FntDocument, BMFontError> {
+        let mut fnt = Vec::new();
+        bmfont.write_fnt(&mut fnt)?;
+        FntDocument::parse(&String::from_utf8_lossy(&fnt))
+    }
+
+    /// Parses the font of this document. Page image paths are kept as they are in the file.
+    ///
+    /// Unknown keys are kept in the `extra` maps rather than being an error, as the document
+    /// keeps them too. Use [`to_bmfont_with`][to_bmfont_with] to parse strictly.
+    ///
+    /// [to_bmfont_with]: #method.to_bmfont_with
+    pub fn to_bmfont(&self) -> Result<BMFont, BMFontError> {
+        self.to_bmfont_with(&mut ParseOptions {
+            strict: false,
+            ..ParseOptions::default()
+        })
+    }
+
+    /// Like [`to_bmfont`][to_bmfont], but with the given [`ParseOptions`][options].
+    ///
+    /// [to_bmfont]: #method.to_bmfont
+    /// [options]: struct.ParseOptions.html
+    pub fn to_bmfont_with(&self, options: &mut ParseOptions) -> Result<BMFont, BMFontError> {
+        bmfont_parser::load(&self.to_string(), options).map(BMFont::from)
+    }
+
+    /// Writes the values of `bmfont` into this document.
+    ///
+    /// Changed values are replaced in place, keys that are missing from a line are only added
+    /// if their value differs from what a missing key means, and unknown keys that are no longer
+    /// in the `extra` maps are removed. Pages, chars and kernings are matched by their ids:
+    /// lines of removed ones are removed and new ones are added after the last line of their
+    /// kind. Existing count lines are updated.
+    pub fn update_from(&mut self, bmfont: &BMFont) {
+        let ends_in_newline = self.lines.last().map_or(true, |line| line.newline);
+        let info = info_pairs(bmfont);
+        let common = common_pairs(bmfont);
+        if !self.lines.iter().any(|line| line.tag() == Some("info")) {
+            self.insert_line(0, Line::new("info", &info));
+        }
+        if !self.lines.iter().any(|line| line.tag() == Some("common")) {
+            let idx = self.insertion_index(&["info"]);
+            self.insert_line(idx, Line::new("common", &common));
+        }
+        for line in self.lines.iter_mut() {
+            match line.tag() {
+                Some("info") => line.apply("info", INFO_KEYS, &info),
+                Some("common") => line.apply("common", COMMON_KEYS, &common),
+                Some("chars") => line.set(&count_pair(bmfont.chars.len())),
+                Some("kernings") => line.set(&count_pair(bmfont.kernings.len())),
+                _ => (),
+            }
+        }
+
+        let mut pages: Vec<_> = bmfont.pages.iter().collect();
+        pages.sort_by_key(|page| page.id);
+        let pages = pages
+            .into_iter()
+            .map(|page| (page.id, page_pairs(page)))
+            .collect();
+        self.update_lines("page", PAGE_KEYS, &["common", "info"], pages, |line| {
+            line.number("id")
+        });
+
+        let mut chars: Vec<_> = bmfont.chars.values().collect();
+        chars.sort_by_key(|c| c.id);
+        let chars = chars.into_iter().map(|c| (c.id, char_pairs(c))).collect();
+        self.update_lines("char", CHAR_KEYS, &["chars", "page"], chars, |line| {
+            line.number("id").map(|id: CharId| id.0)
+        });
+
+        let has_count_lines = self.lines.iter().any(|line| line.tag() == Some("chars"));
+        let has_kernings_line = self.lines.iter().any(|line| line.tag() == Some("kernings"));
+        if has_count_lines && !has_kernings_line && !bmfont.kernings.is_empty() {
+            let idx = self.insertion_index(&["kerning", "char", "chars"]);
+            let line = Line::new("kernings", &[count_pair(bmfont.kernings.len())]);
+            self.insert_line(idx, line);
+        }
+
+        let mut kernings: Vec<_> = bmfont.kernings.iter().collect();
+        kernings.sort();
+        let kernings = kernings
+            .into_iter()
+            .map(|(&(first, second), &amount)| {
+                ((first, second), kerning_pairs(first, second, amount))
+            })
+            .collect();
+        self.update_lines(
+            "kerning",
+            KERNING_KEYS,
+            &["kernings", "char"],
+            kernings,
+            |line| Some((line.number("first")?, line.number("second")?)),
+        );
+
+        // Keep a missing newline at the end of the file missing
+        if !ends_in_newline {
+            if let Some(line) = self.lines.last_mut() {
+                line.newline = false;
+                if line.ends_in_carriage_return() {
+                    line.tokens.pop();
+                }
+            }
+        }
+    }
+
+    /// Write this document into `w`.
+    pub fn write<W: Write>(&self, mut w: W) -> Result<(), BMFontError> {
+        write!(w, "{}", self)?;
+        Ok(())
+    }
+
+    /// Updates, removes and adds the lines with the given tag, keyed by `key`. New lines are
+    /// added after the last line with the tag, or otherwise after the last line of `fallbacks`.
+    fn update_lines<K, F>(
+        &mut self,
+        tag: &str,
+        known: &[&str],
+        fallbacks: &[&str],
+        items: Vec<(K, Vec<Pair>)>,
+        key: F,
+    ) where
+        K: Eq + Hash + Clone,
+        F: Fn(&Line) -> Option<K>,
+    {
+        let mut remaining: HashMap<K, &[Pair]> = items
+            .iter()
+            .map(|(item_key, pairs)| (item_key.clone(), pairs.as_slice()))
+            .collect();
+        self.lines.retain_mut(|line| {
+            if line.tag() != Some(tag) {
+                return true;
+            }
+            match key(line).and_then(|line_key| remaining.remove(&line_key)) {
+                Some(pairs) => {
+                    line.apply(tag, known, pairs);
+                    true
+                }
+                None => false,
+            }
+        });
+
+        let mut tags = vec![tag];
+        tags.extend_from_slice(fallbacks);
+        let mut idx = self.insertion_index(&tags);
+        for (item_key, pairs) in items.iter() {
+            if remaining.contains_key(item_key) {
+                self.insert_line(idx, Line::new(tag, pairs));
+                idx += 1;
+            }
+        }
+    }
+
+    /// Returns the index after the last line with the first of `tags` that is found.
+    fn insertion_index(&self, tags: &[&str]) -> usize {
+        tags.iter()
+            .find_map(|tag| {
+                self.lines
+                    .iter()
+                    .rposition(|line| line.tag() == Some(*tag))
+                    .map(|idx| idx + 1)
+            })
+            .unwrap_or(self.lines.len())
+    }
+
+    /// Inserts `line` at `idx`, ending it like the lines around it.
+    fn insert_line(&mut self, idx: usize, mut line: Line) {
+        if let Some(previous) = idx.checked_sub(1).and_then(|idx| self.lines.get_mut(idx)) {
+            previous.newline = true;
+            if previous.ends_in_carriage_return() {
+                line.tokens.push(Token::Space("\r".to_owned()));
+            }
+        } else if self
+            .lines
+            .first()
+            .is_some_and(Line::ends_in_carriage_return)
+        {
+            line.tokens.push(Token::Space("\r".to_owned()));
+        }
+        self.lines.insert(idx, line);
+    }
+}
+
+impl Display for FntDocument {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        for line in self.lines.iter() {
+            for token in line.tokens.iter() {
+                match token {
+                    Token::Space(text) | Token::Word(text) => write!(f, "{}", text)?,
+                    Token::Pair(pair) => write!(f, "{}", pair)?,
+                }
+            }
+            if line.newline {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Line {
+    fn new(tag: &str, pairs: &[Pair]) -> Line {
+        let mut tokens = vec![Token::Word(tag.to_owned())];
+        for pair in pairs {
+            tokens.push(Token::Space(" ".to_owned()));
+            tokens.push(Token::Pair(pair.clone()));
+        }
+        Line {
+            tokens,
+            newline: true,
+        }
+    }
+
+    fn ends_in_carriage_return(&self) -> bool {
+        matches!(self.tokens.last(), Some(Token::Space(space)) if space.ends_with('\r'))
+    }
+
+    fn tag(&self) -> Option<&str> {
+        self.tokens.iter().find_map(|token| match token {
+            Token::Word(word) => Some(word.as_str()),
+            _ => None,
+        })
+    }
+
+    fn pair(&self, key: &str) -> Option<&Pair> {
+        self.tokens.iter().find_map(|token| match token {
+            Token::Pair(pair) if pair.key == key => Some(pair),
+            _ => None,
+        })
+    }
+
+    fn number<T: std::str::FromStr + Default>(&self, key: &str) -> Option<T> {
+        match self.pair(key) {
+            Some(pair) => pair.value.parse().ok(),
+            None => Some(T::default()),
+        }
+    }
+
+    /// Writes `pairs` into this line. Known keys that are missing are only added if their value
+    /// is not what a missing key means, and unknown keys that are not in `pairs` are removed.
+    fn apply(&mut self, tag: &str, known: &[&str], pairs: &[Pair]) {
+        for pair in pairs {
+            let is_known = known.contains(&pair.key.as_str());
+            if !is_known || self.pair(&pair.key).is_some() || pair.value != missing(tag, &pair.key)
+            {
+                self.set(pair);
+            }
+        }
+        let stale: Vec<String> = self
+            .tokens
+            .iter()
+            .filter_map(|token| match token {
+                Token::Pair(pair)
+                    if !known.contains(&pair.key.as_str())
+                        && !pairs.iter().any(|new| new.key == pair.key) =>
+                {
+                    Some(pair.key.clone())
+                }
+                _ => None,
+            })
+            .collect();
+        for key in stale {
+            self.remove(&key);
+        }
+    }
+
+    /// Replaces the value of the pair's key, keeping its quoting if possible, or adds the pair
+    /// after the last key.
+    fn set(&mut self, new: &Pair) {
+        for token in self.tokens.iter_mut() {
+            if let Token::Pair(pair) = token {
+                if pair.key == new.key {
+                    if pair.value != new.value {
+                        pair.value = new.value.clone();
+                        pair.quoted |=
+                            new.value.is_empty() || new.value.contains(char::is_whitespace);
+                    }
+                    return;
+                }
+            }
+        }
+        let idx = self
+            .tokens
+            .iter()
+            .rposition(|token| !matches!(token, Token::Space(_)))
+            .map_or(0, |idx| idx + 1);
+        self.tokens.insert(idx, Token::Pair(new.clone()));
+        self.tokens.insert(idx, Token::Space(" ".to_owned()));
+    }
+
+    /// Removes the pair with the given key, along with the whitespace before it.
+    fn remove(&mut self, key: &str) {
+        let idx = self
+            .tokens
+            .iter()
+            .position(|token| matches!(token, Token::Pair(pair) if pair.key == key));
+        if let Some(idx) = idx {
+            self.tokens.remove(idx);
+            if idx > 0 && matches!(self.tokens[idx - 1], Token::Space(_)) {
+                self.tokens.remove(idx - 1);
+            }
+        }
+    }
+}
+
+fn count_pair(count: usize) -> Pair {
+    Pair {
+        key: "count".to_owned(),
+        value: count.to_string(),
+        quoted: false,
+    }
+}
+
+/// Returns the value the parser uses for a known key that is missing.
+fn missing(tag: &str, key: &str) -> &'static str {
+    match (tag, key) {
+        ("info", "face") | ("info", "charset") | ("page", "file") => "",
+        ("info", "padding") => "0,0,0,0",
+        ("info", "spacing") => "0,0",
+        ("char", "chnl") => "15",
+        _ => "0",
+    }
+}
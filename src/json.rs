@@ -0,0 +1,247 @@
+//! A minimal JSON reader, enough to read the JSON BMFont layout of msdf-bmfont-xml and similar
+//! tools.
+
+use crate::{result_or, syntax_err, BMFontError};
+
+/// How deep arrays and objects may be nested, so that a malicious file can not overflow the
+/// stack.
+const MAX_DEPTH: usize = 128;
+
+/// A parsed JSON value. Objects keep the order of their keys.
+#[derive(Debug, Clone)]
+pub(crate) enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Returns the value of `key`, if this is an object containing it.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Returns the keys and values of this object, or nothing if this is not an object.
+    pub fn entries(&self) -> &[(String, Value)] {
+        match self {
+            Value::Object(entries) => entries,
+            _ => &[],
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(text) => Some(text),
+            _ => None,
+        }
+    }
+}
+
+/// Parses the given JSON document.
+pub(crate) fn parse(text: &str) -> Result<Value, BMFontError> {
+    let mut reader = Reader {
+        text,
+        cursor: 0,
+        depth: 0,
+    };
+    reader.skip("\u{feff}");
+    let value = reader.value()?;
+    reader.skip_whitespace();
+    if reader.cursor != text.len() {
+        syntax_err("Something found after the JSON value, should not")
+    } else {
+        Ok(value)
+    }
+}
+
+struct Reader<'a> {
+    text: &'a str,
+    cursor: usize,
+    /// The number of arrays and objects the cursor is in
+    depth: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn rest(&self) -> &'a str {
+        &self.text[self.cursor..]
+    }
+
+    fn skip(&mut self, expected: &str) -> bool {
+        if self.rest().starts_with(expected) {
+            self.cursor += expected.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.cursor += rest.len() - rest.trim_start().len();
+    }
+
+    fn value(&mut self) -> Result<Value, BMFontError> {
+        if self.depth == MAX_DEPTH {
+            return syntax_err(format!("JSON values are nested deeper than {}", MAX_DEPTH));
+        }
+        self.depth += 1;
+        let value = self.value_contents();
+        self.depth -= 1;
+        value
+    }
+
+    fn value_contents(&mut self) -> Result<Value, BMFontError> {
+        self.skip_whitespace();
+        if self.skip("{") {
+            let mut entries = Vec::new();
+            self.skip_whitespace();
+            if self.skip("}") {
+                return Ok(Value::Object(entries));
+            }
+            loop {
+                self.skip_whitespace();
+                let key = self.string()?;
+                self.skip_whitespace();
+                if !self.skip(":") {
+                    return syntax_err(format!("Expected \":\" after \"{}\" in JSON", key));
+                }
+                entries.push((key, self.value()?));
+                self.skip_whitespace();
+                if self.skip("}") {
+                    return Ok(Value::Object(entries));
+                } else if !self.skip(",") {
+                    return syntax_err("Expected \",\" or \"}\" in JSON object");
+                }
+            }
+        } else if self.skip("[") {
+            let mut values = Vec::new();
+            self.skip_whitespace();
+            if self.skip("]") {
+                return Ok(Value::Array(values));
+            }
+            loop {
+                values.push(self.value()?);
+                self.skip_whitespace();
+                if self.skip("]") {
+                    return Ok(Value::Array(values));
+                } else if !self.skip(",") {
+                    return syntax_err("Expected \",\" or \"]\" in JSON array");
+                }
+            }
+        } else if self.rest().starts_with('"') {
+            Ok(Value::String(self.string()?))
+        } else if self.skip("true") {
+            Ok(Value::Bool(true))
+        } else if self.skip("false") {
+            Ok(Value::Bool(false))
+        } else if self.skip("null") {
+            Ok(Value::Null)
+        } else {
+            self.number()
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, BMFontError> {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_ascii_digit() || "+-.eE".contains(c)))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return syntax_err("Expected a value in JSON");
+        }
+        self.cursor += len;
+        let number = result_or(rest[..len].parse(), "Unable to parse number in JSON")?;
+        Ok(Value::Number(number))
+    }
+
+    fn string(&mut self) -> Result<String, BMFontError> {
+        if !self.skip("\"") {
+            return syntax_err("Expected a string in JSON");
+        }
+        let mut text = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((idx, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.cursor += idx + 1;
+                    return Ok(text);
+                }
+                '\\' => {
+                    let escaped = match chars.next() {
+                        Some((_, 'u')) => {
+                            let code = hex_escape(&mut chars)?;
+                            if (0xD800..0xDC00).contains(&code) {
+                                let low = match (chars.next(), chars.next()) {
+                                    (Some((_, '\\')), Some((_, 'u'))) => hex_escape(&mut chars)?,
+                                    _ => return syntax_err("Unpaired surrogate in JSON string"),
+                                };
+                                if !(0xDC00..0xE000).contains(&low) {
+                                    return syntax_err("Unpaired surrogate in JSON string");
+                                }
+                                0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00)
+                            } else {
+                                code
+                            }
+                        }
+                        Some((_, 'n')) => '\n' as u32,
+                        Some((_, 't')) => '\t' as u32,
+                        Some((_, 'r')) => '\r' as u32,
+                        Some((_, 'b')) => 0x08,
+                        Some((_, 'f')) => 0x0C,
+                        Some((_, c)) if c == '"' || c == '\\' || c == '/' => c as u32,
+                        _ => return syntax_err("Invalid escape in JSON string"),
+                    };
+                    match std::char::from_u32(escaped) {
+                        Some(c) => text.push(c),
+                        None => return syntax_err("Invalid escape in JSON string"),
+                    }
+                }
+                c => text.push(c),
+            }
+        }
+        syntax_err("Unterminated string in JSON")
+    }
+}
+
+fn hex_escape(chars: &mut std::str::CharIndices) -> Result<u32, BMFontError> {
+    let mut code = 0;
+    for _ in 0..4 {
+        match chars.next().and_then(|(_, c)| c.to_digit(16)) {
+            Some(digit) => code = code * 16 + digit,
+            None => return syntax_err("Invalid unicode escape in JSON string"),
+        }
+    }
+    Ok(code)
+}
+
+/// Returns `text` as a quoted and escaped JSON string.
+pub(crate) fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len() + 2);
+    result.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => result += "\\\"",
+            '\\' => result += "\\\\",
+            '\n' => result += "\\n",
+            '\r' => result += "\\r",
+            '\t' => result += "\\t",
+            c if (c as u32) < 0x20 => result += &format!("\\u{:04x}", c as u32),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
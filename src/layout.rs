@@ -0,0 +1,606 @@
+use crate::{BMCharacter, BMFont, MissingGlyphPolicy};
+
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+/// Options for [`BMFont::layout`][layout].
+///
+/// [layout]: struct.BMFont.html#method.layout
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub struct LayoutOptions {
+    /// Where the text is placed: the top left corner of the box the text is aligned in, but
+    /// with `y` where [`vertical_align`][vertical_align] says, by default the baseline of the
+    /// first line.
+    ///
+    /// [vertical_align]: #structfield.vertical_align
+    pub origin: [f32; 2],
+    /// How much the font is scaled. Defaults to 1.
+    pub scale: f32,
+    /// Whether the kerning pairs of the font are applied. Defaults to `true`.
+    pub kerning: bool,
+    /// The width lines are wrapped to, after scaling. Lines are only broken at `\n` if this is
+    /// `None`, which is the default.
+    pub max_width: Option<f32>,
+    /// Where lines may be wrapped. Defaults to [`LineBreaks::Whitespace`][whitespace].
+    ///
+    /// [whitespace]: enum.LineBreaks.html#variant.Whitespace
+    pub line_breaks: LineBreaks,
+    /// How lines are placed in the width of the box, which is `max_width` or otherwise the
+    /// width of the widest line. Defaults to [`Align::Left`][left].
+    ///
+    /// [left]: enum.Align.html#variant.Left
+    pub align: Align,
+    /// Which part of the text is at the `y` of the origin. Defaults to
+    /// [`VerticalAlign::Baseline`][baseline].
+    ///
+    /// [baseline]: enum.VerticalAlign.html#variant.Baseline
+    pub vertical_align: VerticalAlign,
+    /// What is drawn for characters the font does not have. Defaults to
+    /// [`MissingGlyphPolicy::Skip`][skip].
+    ///
+    /// [skip]: enum.MissingGlyphPolicy.html#variant.Skip
+    pub missing_glyph: MissingGlyphPolicy,
+}
+
+impl Default for LayoutOptions {
+    fn default() -> LayoutOptions {
+        LayoutOptions {
+            origin: [0.0, 0.0],
+            scale: 1.0,
+            kerning: true,
+            max_width: None,
+            line_breaks: LineBreaks::Whitespace,
+            align: Align::Left,
+            vertical_align: VerticalAlign::Baseline,
+            missing_glyph: MissingGlyphPolicy::Skip,
+        }
+    }
+}
+
+/// Where lines may be wrapped when [`LayoutOptions::max_width`][max_width] is set. Words that do
+/// not fit on a line of their own are broken between any two characters.
+///
+/// [max_width]: struct.LayoutOptions.html#structfield.max_width
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
+pub enum LineBreaks {
+    /// After whitespace.
+    Whitespace,
+    /// Where the Unicode line breaking algorithm (UAX #14) allows it, e.g. also between two
+    /// ideographs of CJK text. Needs the `unicode-linebreak` feature, without it lines are
+    /// wrapped after whitespace like [`Whitespace`][whitespace].
+    ///
+    /// [whitespace]: #variant.Whitespace
+    Unicode,
+}
+
+/// How lines are placed horizontally, see [`LayoutOptions::align`][align].
+///
+/// [align]: struct.LayoutOptions.html#structfield.align
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
+pub enum Align {
+    /// Lines start at the left edge of the box.
+    Left,
+    /// Lines are centered in the box.
+    Center,
+    /// Lines end at the right edge of the box.
+    Right,
+    /// Wrapped lines are made as wide as the box by widening their spaces. The lines that end
+    /// a paragraph, and lines without spaces, are aligned left.
+    Justify,
+}
+
+/// Which part of the text is placed at the `y` of [`LayoutOptions::origin`][origin].
+///
+/// [origin]: struct.LayoutOptions.html#structfield.origin
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
+pub enum VerticalAlign {
+    /// The top of the first line, `base` above its baseline.
+    Top,
+    /// The middle of the lines, each of them `line_height` high.
+    Middle,
+    /// The baseline of the first line.
+    Baseline,
+    /// The bottom of the last line.
+    Bottom,
+}
+
+/// A rectangle, with `y` growing downwards.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub struct Rect {
+    /// Left edge
+    pub x: f32,
+    /// Top edge
+    pub y: f32,
+    /// Width
+    pub width: f32,
+    /// Height
+    pub height: f32,
+}
+
+/// A glyph placed by [`BMFont::layout`][layout].
+///
+/// [layout]: struct.BMFont.html#method.layout
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub struct PositionedGlyph {
+    /// The id of the char
+    pub id: u32,
+    /// The byte index of the character in the text
+    pub index: usize,
+    /// The line the glyph is on, counting from 0
+    pub line: usize,
+    /// Where the glyph is drawn
+    pub rect: Rect,
+    /// The part of the page to draw, from 0 to 1. Fonts without
+    /// [`CommonDetails`][common_details] (.sfl fonts), or whose `scale_w` or `scale_h` is 0, do
+    /// not know the size of their pages, so the smallest size holding all of their glyphs is
+    /// used.
+    ///
+    /// [common_details]: struct.CommonDetails.html
+    pub uv: Rect,
+    /// The page the glyph is on
+    pub page: u32,
+    /// The channels the glyph is in
+    pub channel: u32,
+}
+
+/// The glyphs and lines of a text placed by [`BMFont::layout_lines`][layout_lines].
+///
+/// [layout_lines]: struct.BMFont.html#method.layout_lines
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub struct TextLayout {
+    /// The placed glyphs, in the order of the text
+    pub glyphs: Vec<PositionedGlyph>,
+    /// The lines of the text
+    pub lines: Vec<LayoutLine>,
+    /// The characters of the text the font does not have, as in
+    /// [`BMFont::missing_chars`][missing_chars]
+    ///
+    /// [missing_chars]: struct.BMFont.html#method.missing_chars
+    pub missing: BTreeSet<char>,
+}
+
+/// A line of a [`TextLayout`][text_layout].
+///
+/// [text_layout]: struct.TextLayout.html
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub struct LayoutLine {
+    /// The bytes of the text on this line, without the whitespace a wrapped line was broken at
+    pub range: Range<usize>,
+    /// The glyphs of this line in [`TextLayout::glyphs`][glyphs]
+    ///
+    /// [glyphs]: struct.TextLayout.html#structfield.glyphs
+    pub glyphs: Range<usize>,
+    /// How far the pen moved on this line, after scaling. Justified lines are as wide as the
+    /// box.
+    pub width: f32,
+    /// Where the line starts, after alignment
+    pub x: f32,
+    /// The `y` of the baseline of the line
+    pub baseline: f32,
+}
+
+/// The size of a text, as returned by [`BMFont::measure`][measure]. All of it is after scaling.
+///
+/// [measure]: struct.BMFont.html#method.measure
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub struct TextMetrics {
+    /// How far the pen moves on the widest line
+    pub width: f32,
+    /// The height of the lines, `line_height` each
+    pub height: f32,
+    /// The box around the drawn parts of the glyphs, placed like the layout places them, or
+    /// `None` if nothing is drawn
+    pub ink: Option<Rect>,
+    /// The number of lines
+    pub line_count: usize,
+    /// How far a line reaches above its baseline, which is `base`
+    pub ascent: f32,
+    /// How far a line reaches below its baseline, which is `line_height` minus `base`
+    pub descent: f32,
+    /// How far the pen moves on each line
+    pub line_widths: Vec<f32>,
+}
+
+/// A character of the text, and its glyph if the font has one.
+struct Item<'a> {
+    index: usize,
+    ch: char,
+    glyph: Option<&'a BMCharacter>,
+    /// Whether the line may be wrapped before this character
+    break_before: bool,
+}
+
+impl BMFont {
+    /// Places the glyphs of `text`, starting a new line `line_height` lower at every `\n`.
+    /// Glyphs hang from the baseline by `base` and are moved by their offsets, and the pen moves
+    /// by `xadvance` plus the kerning with the previous character.
+    ///
+    /// Characters the font does not have are skipped, unless
+    /// [`missing_glyph`][missing_glyph] says otherwise. Control characters are only drawn if the
+    /// font has them. Glyphs without a size, like spaces, are returned too, so that every glyph
+    /// maps back to its character, except for the whitespace wrapped lines are broken at.
+    ///
+    /// # Examples
+    /// ```
+    /// use bmfont_parser::{BMFont, Format, LayoutOptions};
+    ///
+    /// let bmfont = BMFont::from_path(&Format::BMFont, "examples/fonts/iosevka.fnt").unwrap();
+    /// let glyphs = bmfont.layout("Hi\nthere", &LayoutOptions::default());
+    ///
+    /// assert_eq!(glyphs.len(), 7);
+    /// assert_eq!(glyphs[2].line, 1);
+    /// ```
+    ///
+    /// [missing_glyph]: struct.LayoutOptions.html#structfield.missing_glyph
+    pub fn layout(&self, text: &str, options: &LayoutOptions) -> Vec<PositionedGlyph> {
+        self.layout_lines(text, options).glyphs
+    }
+
+    /// Like [`layout`][layout], but also returns where each line is in the text, where it is
+    /// placed and how wide it is.
+    ///
+    /// With [`max_width`][max_width] set, lines are wrapped at the last place
+    /// [`line_breaks`][line_breaks] allows before they get too wide. Words that are too wide
+    /// for a line of their own are broken between characters. The lines are then aligned as
+    /// [`align`][align] and [`vertical_align`][vertical_align] say.
+    ///
+    /// # Examples
+    /// ```
+    /// use bmfont_parser::{BMFont, Format, LayoutOptions};
+    ///
+    /// let bmfont = BMFont::from_path(&Format::BMFont, "examples/fonts/iosevka.fnt").unwrap();
+    /// let options = LayoutOptions {
+    ///     max_width: Some(150.0),
+    ///     ..LayoutOptions::default()
+    /// };
+    /// let layout = bmfont.layout_lines("Hello there, world", &options);
+    ///
+    /// assert_eq!(layout.lines.len(), 3);
+    /// assert_eq!(layout.lines[1].range, 6..12);
+    /// assert!(layout.lines.iter().all(|line| line.width <= 150.0));
+    /// ```
+    ///
+    /// [layout]: #method.layout
+    /// [max_width]: struct.LayoutOptions.html#structfield.max_width
+    /// [line_breaks]: struct.LayoutOptions.html#structfield.line_breaks
+    /// [align]: struct.LayoutOptions.html#structfield.align
+    /// [vertical_align]: struct.LayoutOptions.html#structfield.vertical_align
+    pub fn layout_lines(&self, text: &str, options: &LayoutOptions) -> TextLayout {
+        let mut layout = TextLayout {
+            missing: self.missing_chars(text),
+            ..TextLayout::default()
+        };
+        let page_size = self.page_size();
+        let mut wrapped = Vec::new();
+        let mut offset = 0;
+        for hard_line in text.split('\n') {
+            let items = self.items(hard_line, offset, options);
+            let ranges = self.wrap(&items, options);
+            wrapped.extend((1..=ranges.len()).map(|n| n < ranges.len()));
+            for range in ranges {
+                let line = &items[range];
+                let range = match (line.first(), line.last()) {
+                    (Some(first), Some(last)) => first.index..last.index + last.ch.len_utf8(),
+                    _ => offset..offset,
+                };
+                self.place_line(line, range, page_size, &mut layout, options);
+            }
+            offset += hard_line.len() + 1;
+        }
+        self.align(text, &mut layout, &wrapped, options);
+        layout
+    }
+
+    /// Measures `text` as [`layout`][layout] places it with the default options.
+    ///
+    /// # Examples
+    /// ```
+    /// use bmfont_parser::{BMFont, Format};
+    ///
+    /// let bmfont = BMFont::from_path(&Format::BMFont, "examples/fonts/iosevka.fnt").unwrap();
+    /// let metrics = bmfont.measure("Hi\nthere");
+    ///
+    /// assert_eq!(metrics.line_count, 2);
+    /// assert_eq!(metrics.line_widths, vec![44.0, 110.0]);
+    /// assert_eq!(metrics.ascent + metrics.descent, 53.0);
+    /// ```
+    ///
+    /// [layout]: #method.layout
+    pub fn measure(&self, text: &str) -> TextMetrics {
+        self.measure_with(text, &LayoutOptions::default())
+    }
+
+    /// Like [`measure`][measure], but measures `text` as it is placed with `options`, wrapped
+    /// and scaled.
+    ///
+    /// [measure]: #method.measure
+    pub fn measure_with(&self, text: &str, options: &LayoutOptions) -> TextMetrics {
+        let layout = self.layout_lines(text, options);
+        let ink = layout
+            .glyphs
+            .iter()
+            .map(|glyph| glyph.rect)
+            .filter(|rect| rect.width > 0.0 && rect.height > 0.0)
+            .reduce(|ink, rect| {
+                let x = ink.x.min(rect.x);
+                let y = ink.y.min(rect.y);
+                Rect {
+                    x,
+                    y,
+                    width: (ink.x + ink.width).max(rect.x + rect.width) - x,
+                    height: (ink.y + ink.height).max(rect.y + rect.height) - y,
+                }
+            });
+        let line_widths: Vec<f32> = layout.lines.iter().map(|line| line.width).collect();
+        TextMetrics {
+            width: line_widths.iter().cloned().fold(0.0, f32::max),
+            height: (layout.lines.len() * self.line_height as usize) as f32 * options.scale,
+            ink,
+            line_count: layout.lines.len(),
+            ascent: self.base() as f32 * options.scale,
+            descent: (self.line_height as f32 - self.base() as f32) * options.scale,
+            line_widths,
+        }
+    }
+
+    /// Looks up the glyphs of a line without `\n`s, which starts at byte `offset` of the text.
+    fn items<'a>(&'a self, line: &str, offset: usize, options: &LayoutOptions) -> Vec<Item<'a>> {
+        let mut items: Vec<Item> = line
+            .char_indices()
+            .map(|(index, ch)| Item {
+                index: offset + index,
+                ch,
+                glyph: match ch.is_control() {
+                    true => self.chars.get(&(ch as u32)),
+                    false => self.glyph(ch, options.missing_glyph),
+                },
+                break_before: false,
+            })
+            .collect();
+        match options.line_breaks {
+            #[cfg(feature = "unicode-linebreak")]
+            LineBreaks::Unicode => {
+                let mut breaks = unicode_linebreak::linebreaks(line).map(|(index, _)| index);
+                let mut next = breaks.next();
+                for item in items.iter_mut() {
+                    while next.is_some_and(|index| index + offset < item.index) {
+                        next = breaks.next();
+                    }
+                    item.break_before = item.index > offset && next == Some(item.index - offset);
+                }
+            }
+            // Without the unicode-linebreak feature, Unicode falls back to this too.
+            _ => {
+                for idx in 1..items.len() {
+                    items[idx].break_before =
+                        items[idx - 1].ch.is_whitespace() && !items[idx].ch.is_whitespace();
+                }
+            }
+        }
+        items
+    }
+
+    /// Splits a line into the ranges of items that fit in `max_width`, leaving out the
+    /// whitespace at the end of wrapped lines.
+    fn wrap(&self, items: &[Item], options: &LayoutOptions) -> Vec<Range<usize>> {
+        let max_width = match options.max_width {
+            Some(max_width) => max_width,
+            None => return std::iter::once(0..items.len()).collect(),
+        };
+        let trimmed_end = |start: usize, end: usize| {
+            (start..end)
+                .rev()
+                .find(|&idx| !items[idx].ch.is_whitespace())
+                .map_or(start, |idx| idx + 1)
+        };
+
+        let mut lines = Vec::new();
+        let mut start = 0;
+        let mut pen = 0;
+        let mut previous = None;
+        let mut idx = 0;
+        while idx < items.len() {
+            let item = &items[idx];
+            let glyph = match item.glyph {
+                Some(glyph) => glyph,
+                None => {
+                    idx += 1;
+                    continue;
+                }
+            };
+            let end = pen + self.kerning_after(previous, glyph.id, options) + glyph.xadvance;
+            if end as f32 * options.scale > max_width && idx > start && !item.ch.is_whitespace() {
+                let wrap_at = (start + 1..=idx)
+                    .rev()
+                    .find(|&at| items[at].break_before && trimmed_end(start, at) > start);
+                let (line_end, next) = match wrap_at {
+                    Some(at) => (trimmed_end(start, at), at),
+                    None => (idx, idx),
+                };
+                lines.push(start..line_end);
+                start = next;
+                idx = next;
+                pen = 0;
+                previous = None;
+                continue;
+            }
+            pen = end;
+            previous = Some(glyph.id);
+            idx += 1;
+        }
+        lines.push(start..items.len());
+        lines
+    }
+
+    /// The kerning between the previous glyph, if any, and the glyph `id`.
+    fn kerning_after(&self, previous: Option<u32>, id: u32, options: &LayoutOptions) -> i32 {
+        match previous {
+            Some(previous) if options.kerning => self.kerning(previous, id),
+            _ => 0,
+        }
+    }
+
+    /// Places the glyphs of a line below the lines already in `layout`.
+    fn place_line(
+        &self,
+        items: &[Item],
+        range: Range<usize>,
+        page_size: [f32; 2],
+        layout: &mut TextLayout,
+        options: &LayoutOptions,
+    ) {
+        let scale = options.scale;
+        let line = layout.lines.len();
+        let baseline = options.origin[1] + (line * self.line_height as usize) as f32 * scale;
+        let top = baseline - self.base() as f32 * scale;
+
+        let first_glyph = layout.glyphs.len();
+        let mut pen = 0;
+        let mut previous = None;
+        for item in items {
+            let c = match item.glyph {
+                Some(c) => c,
+                None => continue,
+            };
+            pen += self.kerning_after(previous, c.id, options);
+            layout.glyphs.push(PositionedGlyph {
+                id: c.id,
+                index: item.index,
+                line,
+                rect: Rect {
+                    x: options.origin[0] + (pen + c.xoffset) as f32 * scale,
+                    y: top + c.yoffset as f32 * scale,
+                    width: c.width as f32 * scale,
+                    height: c.height as f32 * scale,
+                },
+                uv: Rect {
+                    x: c.x as f32 / page_size[0],
+                    y: c.y as f32 / page_size[1],
+                    width: c.width as f32 / page_size[0],
+                    height: c.height as f32 / page_size[1],
+                },
+                page: c.page,
+                channel: c.channel,
+            });
+            pen += c.xadvance;
+            previous = Some(c.id);
+        }
+        layout.lines.push(LayoutLine {
+            range,
+            glyphs: first_glyph..layout.glyphs.len(),
+            width: pen as f32 * scale,
+            x: options.origin[0],
+            baseline,
+        });
+    }
+
+    /// Moves the lines placed from the baseline of the first line where the alignment options
+    /// say. `wrapped` tells for each line whether it was wrapped.
+    fn align(
+        &self,
+        text: &str,
+        layout: &mut TextLayout,
+        wrapped: &[bool],
+        options: &LayoutOptions,
+    ) {
+        let box_width = options.max_width.unwrap_or_else(|| {
+            layout
+                .lines
+                .iter()
+                .map(|line| line.width)
+                .fold(0.0, f32::max)
+        });
+        let height = (layout.lines.len() * self.line_height as usize) as f32 * options.scale;
+        let base = self.base() as f32 * options.scale;
+        let dy = match options.vertical_align {
+            VerticalAlign::Top => base,
+            VerticalAlign::Middle => base - height / 2.0,
+            VerticalAlign::Baseline => 0.0,
+            VerticalAlign::Bottom => base - height,
+        };
+
+        let is_space =
+            |glyph: &PositionedGlyph| text[glyph.index..].starts_with(char::is_whitespace);
+        for (line, &wrapped) in layout.lines.iter_mut().zip(wrapped) {
+            let glyphs = &mut layout.glyphs[line.glyphs.clone()];
+            let free = box_width - line.width;
+            // Spaces after the first word, which are widened when justifying.
+            let spaces = glyphs
+                .iter()
+                .skip_while(|glyph| is_space(glyph))
+                .filter(|glyph| is_space(glyph))
+                .count();
+            let (dx, gap) = match options.align {
+                Align::Left => (0.0, 0.0),
+                Align::Center => (free / 2.0, 0.0),
+                Align::Right => (free, 0.0),
+                Align::Justify if wrapped && spaces > 0 => (0.0, free / spaces as f32),
+                Align::Justify => (0.0, 0.0),
+            };
+
+            let mut shift = dx;
+            let mut started = false;
+            for glyph in glyphs.iter_mut() {
+                glyph.rect.x += shift;
+                glyph.rect.y += dy;
+                if !is_space(glyph) {
+                    started = true;
+                } else if started {
+                    shift += gap;
+                }
+            }
+            if gap > 0.0 {
+                line.width = box_width;
+            }
+            line.x += dx;
+            line.baseline += dy;
+        }
+    }
+
+    /// How far glyphs hang from the baseline. Fonts without common details (.sfl) hang all of
+    /// the line height.
+    fn base(&self) -> u32 {
+        match &self.common_details {
+            Some(common) => common.base,
+            None => self.line_height,
+        }
+    }
+
+    /// The size of the pages that UVs are relative to. A `scale_w` or `scale_h` that is missing
+    /// or 0 is replaced by the smallest size holding all of the glyphs, and by 1 if there are none.
+    fn page_size(&self) -> [f32; 2] {
+        let (scale_w, scale_h) = match &self.common_details {
+            Some(common) => (common.scale_w, common.scale_h),
+            None => (0, 0),
+        };
+        let size = |scale: u32, edge: fn(&BMCharacter) -> u32| {
+            let size = match scale {
+                0 => self.chars.values().map(edge).max().unwrap_or(0),
+                scale => scale,
+            };
+            size.max(1) as f32
+        };
+        [
+            size(scale_w, |c| c.x + c.width),
+            size(scale_h, |c| c.y + c.height),
+        ]
+    }
+}
@@ -18,29 +18,90 @@
 #[cfg(test)]
 mod tests;
 
+mod bmfont_binary_parser;
+mod bmfont_binary_writer;
+mod bmfont_json_parser;
+mod bmfont_json_writer;
 mod bmfont_parser;
+mod bmfont_ref;
+mod bmfont_writer;
+mod bmfont_xml_parser;
+mod bmfont_xml_writer;
+mod diagnostics;
+mod encoding;
+mod error;
+mod fnt_document;
+mod json;
+mod layout;
+mod missing_glyph;
+mod page_resolver;
 mod parser;
+#[cfg(feature = "serde")]
+mod serde_support;
 mod sfl_parser;
+mod sfl_writer;
+mod validate;
+mod xml;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
 use std::fmt::{Display, Formatter};
 use std::fs::File;
-use std::io::{Error, ErrorKind, Read};
-use std::path::PathBuf;
+use std::io::{BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
-pub(crate) fn err<T: Into<String>, U>(text: T) -> Result<U, Error> {
-    Err(Error::new(ErrorKind::Other, text.into()))
+pub use bmfont_ref::{BMFontRef, InfoDetailsRef, PageRef};
+pub use diagnostics::{Diagnostic, DiagnosticLevel, DiagnosticSink, ParseOptions};
+pub use error::{BMFontError, Position, SyntaxError};
+pub use fnt_document::FntDocument;
+pub use layout::{Align, LayoutLine, LayoutOptions, LineBreaks, PositionedGlyph, Rect};
+pub use layout::{TextLayout, TextMetrics, VerticalAlign};
+pub use missing_glyph::MissingGlyphPolicy;
+pub use page_resolver::{resolve_with, ResolveWith};
+pub use page_resolver::{ExplicitList, PageResolver, RelativeToFont, SearchDirectories};
+use parser::Unexpected;
+pub use validate::{IssueKind, Severity, ValidationIssue};
+
+pub(crate) fn syntax_err<T: Into<String>, U>(text: T) -> Result<U, BMFontError> {
+    Err(BMFontError::Syntax(SyntaxError {
+        message: text.into(),
+        expected: Vec::new(),
+        position: None,
+        snippet: None,
+    }))
+}
+
+pub(crate) fn semantic_err<T: Into<String>, U>(text: T) -> Result<U, BMFontError> {
+    Err(BMFontError::Semantic(text.into()))
+}
+
+pub(crate) fn unsupported_err<T: Into<String>, U>(text: T) -> Result<U, BMFontError> {
+    Err(BMFontError::Unsupported(text.into()))
 }
 
-pub(crate) fn result_or<T: Into<String>, U, N>(res: Result<U, N>, text: T) -> Result<U, Error> {
+pub(crate) fn result_or<T: Into<String>, U, N>(
+    res: Result<U, N>,
+    text: T,
+) -> Result<U, BMFontError> {
     match res {
         Ok(val) => Ok(val),
-        Err(_) => Err(Error::new(ErrorKind::Other, text.into())),
+        Err(_) => syntax_err(text),
     }
 }
 
+/// Like `result_or`, but keeps the tokens the parser expected and where in the error.
+pub(crate) fn expect_or<T: Into<String>, U>(
+    res: Result<U, Unexpected>,
+    text: T,
+) -> Result<U, BMFontError> {
+    res.map_err(|unexpected| unexpected.into_error(text))
+}
+
 /// Represents a single character in the bitmap font atlas. Contains coordinates, sizes, offsets and advances (everything required to render letters from the atlas).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct BMCharacter {
     /// char id of the character.
     pub id: u32,
@@ -62,10 +123,54 @@ pub struct BMCharacter {
     pub page: u32,
     /// The texture channel where the character is found
     pub channel: u32,
+    /// Keys of the char block that are not known, kept when parsing leniently
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub extra: BTreeMap<String, String>,
+}
+
+impl BMCharacter {
+    /// The id of the glyph BMFont exports for characters the font does not have. It is written
+    /// as `-1` in text, XML and JSON fonts.
+    pub const INVALID_ID: u32 = 0xFFFF_FFFF;
+}
+
+/// A char id as written in text, XML and JSON fonts, where `-1` is
+/// [`BMCharacter::INVALID_ID`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct CharId(pub u32);
+
+impl FromStr for CharId {
+    type Err = ();
+
+    fn from_str(text: &str) -> Result<CharId, ()> {
+        CharId::try_from(text.parse::<i64>().map_err(|_| ())?)
+    }
+}
+
+impl TryFrom<i64> for CharId {
+    type Error = ();
+
+    fn try_from(id: i64) -> Result<CharId, ()> {
+        match id {
+            -1 => Ok(CharId(BMCharacter::INVALID_ID)),
+            _ => u32::try_from(id).map(CharId).map_err(|_| ()),
+        }
+    }
+}
+
+impl Display for CharId {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self.0 {
+            BMCharacter::INVALID_ID => write!(f, "-1"),
+            id => write!(f, "{}", id),
+        }
+    }
 }
 
 /// Some details from the info block
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct InfoDetails {
     /// Is the font bold
     pub bold: u32,
@@ -87,10 +192,15 @@ pub struct InfoDetails {
     pub spacing: [u32; 2],
     /// Outline thickness
     pub outline: u32,
+    /// Keys of the info block that are not known, kept when parsing leniently
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub extra: BTreeMap<String, String>,
 }
 
 /// Some details from the common block
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct CommonDetails {
     /// Number of pixels from the absolute top of the line to the base.
     pub base: u32,
@@ -126,10 +236,28 @@ pub struct CommonDetails {
     /// 3 = set to zero  
     /// 4 = set to one
     pub blue_channel: u32,
+    /// Keys of the common block that are not known, kept when parsing leniently
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub extra: BTreeMap<String, String>,
+}
+
+/// Distance field details, found in fonts made for signed distance field rendering
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub struct DistanceField {
+    /// The type of the distance field, e.g. "sdf", "psdf" or "msdf"
+    pub field_type: String,
+    /// The distance range of the field, in pixels
+    pub distance_range: u32,
 }
 
 /// Loaded and parsed struct of an .sfl file (a bitmap font file).
+///
+/// Fonts are equal if their contents are, whatever counts their files declared.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct BMFont {
     // Info
     /// The name of the font.
@@ -144,35 +272,169 @@ pub struct BMFont {
     pub line_height: u32,
     /// Some details from the Common-block that are not available in all parsing methods
     pub common_details: Option<CommonDetails>,
+    /// Distance field details, only available in JSON fonts made for distance field rendering
+    pub distance_field: Option<DistanceField>,
 
     /// The pages of this font
     pub pages: Vec<Page>,
     /// Hashmap of the characters in the font. <CharID, [`BMCharacter`][bmcharacter]>
     ///
     /// [bmcharacter]: struct.BMCharacter.html
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::chars"))]
     pub chars: HashMap<u32, BMCharacter>,
+    /// Hashmap of the kerning pairs in the font. <(first CharID, second CharID), amount>
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::kernings"))]
+    pub kernings: HashMap<(u32, u32), i32>,
+    /// The counts declared in the font file, which may not match what it contains. They are
+    /// kept for [`validate`][validate], the writers write the real counts.
+    ///
+    /// [validate]: #method.validate
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub declared_counts: DeclaredCounts,
+}
+
+impl PartialEq for BMFont {
+    fn eq(&self, other: &BMFont) -> bool {
+        self.font_name == other.font_name
+            && self.size == other.size
+            && self.info_details == other.info_details
+            && self.line_height == other.line_height
+            && self.common_details == other.common_details
+            && self.distance_field == other.distance_field
+            && self.pages == other.pages
+            && self.chars == other.chars
+            && self.kernings == other.kernings
+    }
+}
+
+/// The amounts of characters and kerning pairs declared in a font file, and the char ids it
+/// uses more than once
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub struct DeclaredCounts {
+    /// The count of the chars block, if the font has one
+    pub chars: Option<usize>,
+    /// The count of the kernings block, if the font has one
+    pub kernings: Option<usize>,
+    /// The ids of the chars that appear more than once, of which only the last one is kept
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub duplicate_chars: Vec<u32>,
+}
+
+impl DeclaredCounts {
+    /// Records that a char with `id` was found again, warning that the last one is used.
+    pub(crate) fn duplicate_char(
+        &mut self,
+        id: u32,
+        position: Option<Position>,
+        options: &mut ParseOptions,
+    ) {
+        options.warn(
+            format!("Duplicate char id {}, the last one is used", id),
+            position,
+        );
+        if !self.duplicate_chars.contains(&id) {
+            self.duplicate_chars.push(id);
+        }
+    }
 }
 
 /// The pages (or textures) of the BMFont
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct Page {
     /// The id of this page
     pub id: u32,
     /// The path of the image
     pub image_path: PathBuf,
+    /// Keys of the page block that are not known, kept when parsing leniently
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub extra: BTreeMap<String, String>,
 }
 
 /// Specifies the type of file format which the font file uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
 pub enum Format {
     /// Files ending in .sfl, like those created by FontBuilder
     SFL,
     /// Files ending in .fnt, like those created by BMFont
     BMFont,
+    /// Binary .fnt files (version 3), like those created by BMFont
+    BMFontBinary,
+    /// XML .fnt files, like those created by BMFont, Littera or Phaser
+    BMFontXml,
+    /// JSON files, like those created by msdf-bmfont-xml
+    BMFontJson,
+}
+
+impl Format {
+    /// Detects the format of the given font file contents, or returns `None` if it does not look
+    /// like any of the supported formats.
+    ///
+    /// # Examples
+    /// ```
+    /// use bmfont_parser::Format;
+    ///
+    /// let iosevka_fnt = include_bytes!("../examples/fonts/iosevka.fnt");
+    ///
+    /// assert_eq!(Format::detect(iosevka_fnt), Some(Format::BMFont));
+    /// ```
+    pub fn detect(contents: &[u8]) -> Option<Format> {
+        if contents.starts_with(bmfont_binary_parser::MAGIC) {
+            return Some(Format::BMFontBinary);
+        }
+
+        let contents = contents.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(contents);
+        let start = contents.iter().position(|b| !b.is_ascii_whitespace())?;
+        let contents = &contents[start..];
+        if contents.starts_with(b"info ") || contents.starts_with(b"info\t") {
+            Some(Format::BMFont)
+        } else if contents.starts_with(b"<?xml")
+            || contents.starts_with(b"<font")
+            || contents.starts_with(b"<!--")
+        {
+            Some(Format::BMFontXml)
+        } else if contents.starts_with(b"{") {
+            Some(Format::BMFontJson)
+        } else if Format::is_sfl(contents) {
+            Some(Format::SFL)
+        } else {
+            None
+        }
+    }
+
+    /// Checks for the shape of an .sfl file: a name, the size and line height, an image path
+    /// and the character count on their own lines.
+    fn is_sfl(contents: &[u8]) -> bool {
+        let text = String::from_utf8_lossy(&contents[..contents.len().min(1024)]);
+        let mut lines = text.lines().map(str::trim);
+        let is_number = |text: &str| text.parse::<u32>().is_ok();
+
+        let name = lines.next();
+        let metrics: Vec<&str> = lines.next().unwrap_or("").split_whitespace().collect();
+        let image = lines.next();
+        let count = lines.next();
+
+        name.is_some_and(|name| !name.is_empty())
+            && metrics.len() == 2
+            && metrics.iter().all(|number| is_number(number))
+            && image.is_some_and(|image| !image.is_empty())
+            && count.is_some_and(is_number)
+    }
 }
 
 impl BMFont {
     /// Load and parse a `BMFont` from the given `path`, which should be an .sfl file.
     ///
+    /// The image paths of the pages are made relative to the font file, like
+    /// [`RelativeToFont`][relative] does. Use [`from_reader_with_resolver`][from_reader] with
+    /// another [`PageResolver`][resolver] to find them elsewhere. Text that is not valid UTF-8 is
+    /// decoded as Windows-1252 with a warning, as with every constructor.
+    ///
     /// # Examples
     /// ```
     /// use bmfont_parser::{BMFont, Format};
@@ -184,29 +446,79 @@ impl BMFont {
     ///
     /// println!("bmfont: {}", bmfont);
     /// ```
-    pub fn from_path<T: Into<PathBuf>>(format: &Format, path: T) -> Result<BMFont, Error> {
-        let path = path.into();
-        let mut file = File::open(&path)?;
-
-        let mut buffer = String::new();
-        file.read_to_string(&mut buffer)?;
+    ///
+    /// [relative]: struct.RelativeToFont.html
+    /// [from_reader]: struct.BMFont.html#method.from_reader_with_resolver
+    /// [resolver]: trait.PageResolver.html
+    pub fn from_path<T: Into<PathBuf>>(format: &Format, path: T) -> Result<BMFont, BMFontError> {
+        BMFont::from_path_with(format, path, &mut ParseOptions::default())
+    }
 
-        let mut bmfont = match format {
-            Format::SFL => sfl_parser::load(buffer)?,
-            Format::BMFont => bmfont_parser::load(buffer)?,
-        };
+    /// Like [`from_path`][from_path], but with the given [`ParseOptions`][options].
+    ///
+    /// # Examples
+    /// ```
+    /// use bmfont_parser::{BMFont, Diagnostic, Format, ParseOptions};
+    ///
+    /// let mut warnings = Vec::new();
+    /// let mut sink = |diagnostic: Diagnostic| warnings.push(diagnostic.to_string());
+    /// let mut options = ParseOptions::with_diagnostics(&mut sink);
+    ///
+    /// let bmfont = BMFont::from_path_with(&Format::SFL, "examples/fonts/iosevka.sfl", &mut options);
+    /// assert!(bmfont.is_ok());
+    /// ```
+    ///
+    /// [from_path]: struct.BMFont.html#method.from_path
+    /// [options]: struct.ParseOptions.html
+    pub fn from_path_with<T: Into<PathBuf>>(
+        format: &Format,
+        path: T,
+        options: &mut ParseOptions,
+    ) -> Result<BMFont, BMFontError> {
+        let path = path.into();
+        let buffer = BMFont::read(&path)?;
+        let bmfont = BMFont::parse(format, &buffer, options)?;
+        bmfont.with_resolver(RelativeToFont::new(path))
+    }
 
-        if let Some(path) = path.parent() {
-            for page in bmfont.pages.iter_mut() {
-                let mut image_path = (*path).to_path_buf();
-                image_path.push(page.image_path.clone());
-                page.image_path = image_path;
-            }
+    /// Load and parse a `BMFont` from the given `path`, detecting its format with
+    /// [`Format::detect`][detect].
+    ///
+    /// # Examples
+    /// ```
+    /// use bmfont_parser::BMFont;
+    ///
+    /// let bmfont = match BMFont::from_path_auto("examples/fonts/iosevka.fnt") {
+    ///     Ok(bmfont) => bmfont,
+    ///     Err(_) => panic!("Failed to load iosevka.fnt"),
+    /// };
+    ///
+    /// println!("bmfont: {}", bmfont);
+    /// ```
+    ///
+    /// [detect]: enum.Format.html#method.detect
+    pub fn from_path_auto<T: Into<PathBuf>>(path: T) -> Result<BMFont, BMFontError> {
+        let path = path.into();
+        let buffer = BMFont::read(&path)?;
+        let format = BMFont::detect(&buffer)?;
+        let bmfont = BMFont::parse(&format, &buffer, &mut ParseOptions::default())?;
+        bmfont.with_resolver(RelativeToFont::new(path))
+    }
 
-            Ok(bmfont)
-        } else {
-            err("Unable to retrieve path parent.")
-        }
+    /// Load and parse a `BMFont` from the given `path`, detecting its format. The same as
+    /// [`from_path_auto`][from_path_auto].
+    ///
+    /// # Examples
+    /// ```
+    /// use bmfont_parser::BMFont;
+    ///
+    /// let bmfont = BMFont::load_auto("examples/fonts/iosevka.xml").unwrap();
+    /// assert_eq!(bmfont.font_name, "Iosevka");
+    /// ```
+    ///
+    /// [from_path_auto]: #method.from_path_auto
+    pub fn load_auto<T: Into<PathBuf>>(path: T) -> Result<BMFont, BMFontError> {
+        BMFont::from_path_auto(path)
     }
 
     /// Load and parse a `BMFont` from the given `String`, which should be the contents of an .sfl file.
@@ -228,26 +540,396 @@ impl BMFont {
         format: &Format,
         contents: T,
         image_path: &[&str],
-    ) -> Result<BMFont, Error> {
-        let mut bmfont = match format {
-            Format::SFL => sfl_parser::load(contents)?,
-            Format::BMFont => bmfont_parser::load(contents)?,
+    ) -> Result<BMFont, BMFontError> {
+        BMFont::from_bytes(format, contents.into().as_bytes(), image_path)
+    }
+
+    /// Load and parse a `BMFont` from the given bytes, which should be the contents of a font file
+    /// in the given `format`. This is required for binary formats, which are not valid `String`s.
+    ///
+    /// # Examples
+    /// ```
+    /// use bmfont_parser::{BMFont, Format};
+    ///
+    /// let iosevka_fnt = include_bytes!("../examples/fonts/iosevka-binary.fnt");
+    ///
+    /// let bmfont = match BMFont::from_bytes(&Format::BMFontBinary, iosevka_fnt, &["examples/fonts/iosevka.png"]) {
+    ///     Ok(bmfont) => bmfont,
+    ///     Err(_) => panic!("Failed to load iosevka-binary.fnt"),
+    /// };
+    ///
+    /// println!("bmfont: {}", bmfont);
+    /// ```
+    pub fn from_bytes(
+        format: &Format,
+        contents: &[u8],
+        image_path: &[&str],
+    ) -> Result<BMFont, BMFontError> {
+        BMFont::from_bytes_with(format, contents, image_path, &mut ParseOptions::default())
+    }
+
+    /// Like [`from_bytes`][from_bytes], but with the given [`ParseOptions`][options].
+    ///
+    /// [from_bytes]: struct.BMFont.html#method.from_bytes
+    /// [options]: struct.ParseOptions.html
+    pub fn from_bytes_with(
+        format: &Format,
+        contents: &[u8],
+        image_path: &[&str],
+        options: &mut ParseOptions,
+    ) -> Result<BMFont, BMFontError> {
+        let bmfont = BMFont::parse(format, contents, options)?;
+        bmfont.with_resolver(ExplicitList::new(image_path))
+    }
+
+    /// Load and parse a `BMFont` from the given bytes, detecting its format with
+    /// [`Format::detect`][detect].
+    ///
+    /// # Examples
+    /// ```
+    /// use bmfont_parser::BMFont;
+    ///
+    /// let iosevka_fnt = include_bytes!("../examples/fonts/iosevka-binary.fnt");
+    ///
+    /// let bmfont = match BMFont::from_bytes_auto(iosevka_fnt, &["examples/fonts/iosevka.png"]) {
+    ///     Ok(bmfont) => bmfont,
+    ///     Err(_) => panic!("Failed to load iosevka-binary.fnt"),
+    /// };
+    ///
+    /// println!("bmfont: {}", bmfont);
+    /// ```
+    ///
+    /// [detect]: enum.Format.html#method.detect
+    pub fn from_bytes_auto(contents: &[u8], image_path: &[&str]) -> Result<BMFont, BMFontError> {
+        let format = BMFont::detect(contents)?;
+        let bmfont = BMFont::parse(&format, contents, &mut ParseOptions::default())?;
+        bmfont.with_resolver(ExplicitList::new(image_path))
+    }
+
+    /// Load and parse a `BMFont` from `reader`, which should give the contents of a font file in
+    /// the given `format`. The image path of each page is given by `page_resolver` from the id
+    /// of the page and the file name written in the font.
+    ///
+    /// Text .fnt files are parsed one line at a time, the other formats are read whole before
+    /// parsing. Text that is not valid UTF-8, like the names older versions of BMFont write in
+    /// the system codepage, is decoded as Windows-1252 with a warning.
+    ///
+    /// # Examples
+    /// ```
+    /// use bmfont_parser::{BMFont, Format};
+    /// use std::fs::File;
+    /// use std::path::Path;
+    ///
+    /// let file = File::open("examples/fonts/iosevka.fnt").unwrap();
+    /// let bmfont = BMFont::from_reader(&Format::BMFont, file, |_, file: &Path| {
+    ///     Path::new("textures").join(file)
+    /// })
+    /// .unwrap();
+    ///
+    /// assert_eq!(bmfont.pages[0].image_path, Path::new("textures/iosevka.png"));
+    /// ```
+    pub fn from_reader<R, F>(
+        format: &Format,
+        reader: R,
+        page_resolver: F,
+    ) -> Result<BMFont, BMFontError>
+    where
+        R: Read,
+        F: FnMut(u32, &Path) -> PathBuf,
+    {
+        BMFont::from_reader_with(format, reader, page_resolver, &mut ParseOptions::default())
+    }
+
+    /// Like [`from_reader`][from_reader], but with the given [`ParseOptions`][options].
+    ///
+    /// [from_reader]: struct.BMFont.html#method.from_reader
+    /// [options]: struct.ParseOptions.html
+    pub fn from_reader_with<R, F>(
+        format: &Format,
+        reader: R,
+        page_resolver: F,
+        options: &mut ParseOptions,
+    ) -> Result<BMFont, BMFontError>
+    where
+        R: Read,
+        F: FnMut(u32, &Path) -> PathBuf,
+    {
+        BMFont::from_reader_with_resolver(format, reader, resolve_with(page_resolver), options)
+    }
+
+    /// Like [`from_reader_with`][from_reader_with], but the image paths are given by a
+    /// [`PageResolver`][resolver], like [`SearchDirectories`][search].
+    ///
+    /// [from_reader_with]: struct.BMFont.html#method.from_reader_with
+    /// [resolver]: trait.PageResolver.html
+    /// [search]: struct.SearchDirectories.html
+    pub fn from_reader_with_resolver<R: Read, P: PageResolver>(
+        format: &Format,
+        mut reader: R,
+        page_resolver: P,
+        options: &mut ParseOptions,
+    ) -> Result<BMFont, BMFontError> {
+        let bmfont = match format {
+            Format::BMFont => {
+                let bmfont = bmfont_parser::load_lines(BufReader::new(reader), options)?;
+                BMFont::report_parsed(
+                    format,
+                    bmfont.common_details.as_ref(),
+                    bmfont.pages.len(),
+                    bmfont.chars.len(),
+                    bmfont.kernings.len(),
+                    options,
+                );
+                bmfont
+            }
+            _ => {
+                let mut buffer = Vec::new();
+                reader.read_to_end(&mut buffer)?;
+                BMFont::parse(format, &buffer, options)?
+            }
         };
+        bmfont.with_resolver(page_resolver)
+    }
+}
+
+impl BMFont {
+    fn read(path: &Path) -> Result<Vec<u8>, BMFontError> {
+        let mut file = File::open(path)?;
+
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer)?;
+        Ok(buffer)
+    }
 
-        for (idx, page) in bmfont.pages.iter_mut().enumerate() {
-            let mut pathbuf = PathBuf::new();
-            let path;
-            if let Some(p) = image_path.get(idx) {
-                path = p;
-            } else {
-                return err("Wrong amount of image paths given to accompany each page");
-            };
-            pathbuf.push(path);
-            page.image_path = pathbuf;
+    fn detect(contents: &[u8]) -> Result<Format, BMFontError> {
+        match Format::detect(contents) {
+            Some(format) => Ok(format),
+            None => unsupported_err("Unable to detect the format of the font"),
         }
+    }
 
+    /// Replaces the image paths of the pages with the ones given by `resolver`.
+    fn with_resolver<P: PageResolver>(mut self, resolver: P) -> Result<BMFont, BMFontError> {
+        let paths = self.resolve_pages(resolver)?;
+        for (page, path) in self.pages.iter_mut().zip(paths) {
+            page.image_path = path;
+        }
+        Ok(self)
+    }
+
+    fn parse(
+        format: &Format,
+        contents: &[u8],
+        options: &mut ParseOptions,
+    ) -> Result<BMFont, BMFontError> {
+        let bmfont = match format {
+            Format::SFL => {
+                let text = encoding::decode(contents, None, options);
+                sfl_parser::load(&text, options).map(BMFont::from)
+            }
+            Format::BMFont => {
+                let text = encoding::decode(contents, None, options);
+                bmfont_parser::load(&text, options).map(BMFont::from)
+            }
+            Format::BMFontBinary => bmfont_binary_parser::load(contents, options),
+            Format::BMFontXml => {
+                let text = encoding::decode(contents, None, options);
+                bmfont_xml_parser::load(&text, options)
+            }
+            Format::BMFontJson => {
+                let text = encoding::decode(contents, None, options);
+                bmfont_json_parser::load(&text, options)
+            }
+        }?;
+
+        BMFont::report_parsed(
+            format,
+            bmfont.common_details.as_ref(),
+            bmfont.pages.len(),
+            bmfont.chars.len(),
+            bmfont.kernings.len(),
+            options,
+        );
         Ok(bmfont)
     }
+
+    /// Reports a page count that does not match `common.pages`, and what was parsed.
+    pub(crate) fn report_parsed(
+        format: &Format,
+        common_details: Option<&CommonDetails>,
+        pages: usize,
+        chars: usize,
+        kernings: usize,
+        options: &mut ParseOptions,
+    ) {
+        if let Some(com) = common_details {
+            if com.pages_count as usize != pages {
+                options.warn(
+                    format!(
+                        "common.pages is {}, but {} pages were found",
+                        com.pages_count, pages
+                    ),
+                    None,
+                );
+            }
+        }
+        options.debug(
+            format!(
+                "Parsed {:?} font with {} pages, {} chars and {} kernings",
+                format, pages, chars, kernings
+            ),
+            None,
+        );
+    }
+
+    /// Write this font in the text .fnt format of AngelCode BMFont into `w`.
+    ///
+    /// Page image paths are written as they are. If the font has no
+    /// [`info_details`][info] or [`common_details`][common], as with fonts loaded from .sfl files,
+    /// sensible defaults are written in their place. Unknown keys kept in the `extra` maps are
+    /// written after the known ones. Values containing a `"` can not be written, and give a
+    /// [`BMFontError::Unsupported`][unsupported].
+    ///
+    /// # Examples
+    /// ```
+    /// use bmfont_parser::{BMFont, Format};
+    ///
+    /// let bmfont = BMFont::from_path(&Format::SFL, "examples/fonts/iosevka.sfl").unwrap();
+    ///
+    /// let mut fnt = Vec::new();
+    /// bmfont.write_fnt(&mut fnt).unwrap();
+    ///
+    /// assert!(fnt.starts_with(b"info face=\"Iosevka\""));
+    /// ```
+    ///
+    /// [info]: struct.BMFont.html#structfield.info_details
+    /// [common]: struct.BMFont.html#structfield.common_details
+    /// [unsupported]: enum.BMFontError.html#variant.Unsupported
+    pub fn write_fnt<W: Write>(&self, w: W) -> Result<(), BMFontError> {
+        bmfont_writer::write(self, w)
+    }
+
+    /// Write this font in the .sfl format of FontBuilder into `w`.
+    ///
+    /// The .sfl format only holds the name, size, line height, a single page and the character
+    /// metrics, so the info and common details are not written. Returns an error instead of
+    /// writing anything if the font has more than one page, characters with a channel other than
+    /// 15, or kernings, as those can not be represented.
+    ///
+    /// # Examples
+    /// ```
+    /// use bmfont_parser::{BMFont, Format};
+    ///
+    /// let bmfont = BMFont::from_path(&Format::BMFont, "examples/fonts/iosevka.fnt").unwrap();
+    ///
+    /// let mut sfl = Vec::new();
+    /// bmfont.write_sfl(&mut sfl).unwrap();
+    ///
+    /// assert!(sfl.starts_with(b"Iosevka\n32 53\n"));
+    /// ```
+    pub fn write_sfl<W: Write>(&self, w: W) -> Result<(), BMFontError> {
+        sfl_writer::write(self, w)
+    }
+
+    /// Write this font in the binary .fnt format (version 3) of AngelCode BMFont into `w`.
+    ///
+    /// Like with [`write_fnt`][write_fnt], defaults are written in place of missing info and
+    /// common details. Returns an error if a value does not fit in the size the binary format
+    /// has for it, if page ids are not consecutive from 0, or if the page file names are not all
+    /// of the same length.
+    ///
+    /// # Examples
+    /// ```
+    /// use bmfont_parser::{BMFont, Format};
+    ///
+    /// let bmfont = BMFont::from_path(&Format::BMFont, "examples/fonts/iosevka.fnt").unwrap();
+    ///
+    /// let mut fnt = Vec::new();
+    /// bmfont.write_binary(&mut fnt).unwrap();
+    ///
+    /// assert!(fnt.starts_with(b"BMF\x03"));
+    /// ```
+    ///
+    /// [write_fnt]: #method.write_fnt
+    pub fn write_binary<W: Write>(&self, w: W) -> Result<(), BMFontError> {
+        bmfont_binary_writer::write(self, w)
+    }
+
+    /// Write this font in the XML .fnt format of AngelCode BMFont into `w`.
+    ///
+    /// Like with [`write_fnt`][write_fnt], defaults are written in place of missing info and
+    /// common details, and the `extra` maps are written as attributes.
+    ///
+    /// [write_fnt]: #method.write_fnt
+    pub fn write_xml<W: Write>(&self, w: W) -> Result<(), BMFontError> {
+        bmfont_xml_writer::write(self, w)
+    }
+
+    /// Write this font in the JSON layout of msdf-bmfont-xml into `w`, including the
+    /// [`distance_field`][distance_field] details if the font has them.
+    ///
+    /// Like with [`write_fnt`][write_fnt], defaults are written in place of missing info and
+    /// common details, and the `extra` maps are written as string members. Pages are written as
+    /// file names only, so a page with `extra` keys gives a
+    /// [`BMFontError::Unsupported`][unsupported].
+    ///
+    /// [write_fnt]: #method.write_fnt
+    /// [unsupported]: enum.BMFontError.html#variant.Unsupported
+    /// [distance_field]: struct.BMFont.html#structfield.distance_field
+    pub fn write_json<W: Write>(&self, w: W) -> Result<(), BMFontError> {
+        bmfont_json_writer::write(self, w)
+    }
+
+    /// Write this font in the given `format` into `w`.
+    ///
+    /// # Examples
+    /// ```
+    /// use bmfont_parser::{BMFont, Format};
+    ///
+    /// let bmfont = BMFont::from_path(&Format::SFL, "examples/fonts/iosevka.sfl").unwrap();
+    ///
+    /// let mut xml = Vec::new();
+    /// bmfont.write(&Format::BMFontXml, &mut xml).unwrap();
+    ///
+    /// assert_eq!(Format::detect(&xml), Some(Format::BMFontXml));
+    /// ```
+    pub fn write<W: Write>(&self, format: &Format, w: W) -> Result<(), BMFontError> {
+        match format {
+            Format::SFL => self.write_sfl(w),
+            Format::BMFont => self.write_fnt(w),
+            Format::BMFontBinary => self.write_binary(w),
+            Format::BMFontXml => self.write_xml(w),
+            Format::BMFontJson => self.write_json(w),
+        }
+    }
+
+    /// Returns the kerning amount (in pixels) to apply between the characters `first` and `second`,
+    /// or 0 if the font has no kerning pair for them.
+    ///
+    /// # Examples
+    /// ```
+    /// use bmfont_parser::{BMFont, Format};
+    ///
+    /// let bmfont = BMFont::from_path(&Format::BMFont, "examples/fonts/iosevka.fnt").unwrap();
+    ///
+    /// assert_eq!(bmfont.kerning('A' as u32, 'V' as u32), 0);
+    /// ```
+    pub fn kerning(&self, first: u32, second: u32) -> i32 {
+        self.kernings.get(&(first, second)).cloned().unwrap_or(0)
+    }
+
+    /// Returns the image of each page given by `resolver`, in the order of the pages, from the
+    /// id of the page and its current image path. The resolver can give any kind of handle, like
+    /// a texture loaded by an asset system.
+    pub fn resolve_pages<T, P: PageResolver<T>>(
+        &self,
+        mut resolver: P,
+    ) -> Result<Vec<T>, BMFontError> {
+        self.pages
+            .iter()
+            .map(|page| resolver.resolve(page.id, &page.image_path))
+            .collect()
+    }
 }
 
 impl Display for BMFont {
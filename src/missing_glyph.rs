@@ -0,0 +1,81 @@
+use crate::{BMCharacter, BMFont};
+
+use std::collections::BTreeSet;
+
+/// What is drawn for characters a font does not have, when looking glyphs up with
+/// [`BMFont::glyph`][glyph] or laying text out. If the font does not have the replacement
+/// either, nothing is drawn.
+///
+/// [glyph]: struct.BMFont.html#method.glyph
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
+pub enum MissingGlyphPolicy {
+    /// Nothing is drawn. This is the default.
+    #[default]
+    Skip,
+    /// U+FFFD REPLACEMENT CHARACTER is drawn.
+    ReplacementChar,
+    /// A `?` is drawn.
+    QuestionMark,
+    /// The char with this id is drawn.
+    Char(u32),
+    /// The glyph BMFont exports for invalid characters is drawn, which has the id `-1`, or
+    /// [`BMCharacter::INVALID_ID`][invalid_id].
+    ///
+    /// [invalid_id]: struct.BMCharacter.html#associatedconstant.INVALID_ID
+    InvalidGlyph,
+}
+
+impl MissingGlyphPolicy {
+    /// The id of the char drawn instead of missing ones, if any.
+    fn replacement(self) -> Option<u32> {
+        match self {
+            MissingGlyphPolicy::Skip => None,
+            MissingGlyphPolicy::ReplacementChar => Some(0xFFFD),
+            MissingGlyphPolicy::QuestionMark => Some('?' as u32),
+            MissingGlyphPolicy::Char(id) => Some(id),
+            MissingGlyphPolicy::InvalidGlyph => Some(BMCharacter::INVALID_ID),
+        }
+    }
+}
+
+impl BMFont {
+    /// Returns the glyph of `ch`, or the one `policy` gives if the font does not have it.
+    ///
+    /// # Examples
+    /// ```
+    /// use bmfont_parser::{BMFont, Format, MissingGlyphPolicy};
+    ///
+    /// let bmfont = BMFont::from_path(&Format::BMFont, "examples/fonts/iosevka.fnt").unwrap();
+    ///
+    /// assert_eq!(bmfont.glyph('A', MissingGlyphPolicy::Skip).unwrap().id, 'A' as u32);
+    /// assert!(bmfont.glyph('你', MissingGlyphPolicy::Skip).is_none());
+    /// let glyph = bmfont.glyph('你', MissingGlyphPolicy::QuestionMark).unwrap();
+    /// assert_eq!(glyph.id, '?' as u32);
+    /// ```
+    pub fn glyph(&self, ch: char, policy: MissingGlyphPolicy) -> Option<&BMCharacter> {
+        self.chars
+            .get(&(ch as u32))
+            .or_else(|| self.chars.get(&policy.replacement()?))
+    }
+
+    /// Returns the characters of `text` the font does not have, e.g. to find the text a font
+    /// still needs for a translation. Control characters, like `\n`, are left out, as
+    /// layout does not draw them.
+    ///
+    /// # Examples
+    /// ```
+    /// use bmfont_parser::{BMFont, Format};
+    ///
+    /// let bmfont = BMFont::from_path(&Format::BMFont, "examples/fonts/iosevka.fnt").unwrap();
+    /// let missing: Vec<char> = bmfont.missing_chars("Hello ☃ 你好\r\n").into_iter().collect();
+    ///
+    /// assert_eq!(missing, vec!['☃', '你', '好']);
+    /// ```
+    pub fn missing_chars(&self, text: &str) -> BTreeSet<char> {
+        text.chars()
+            .filter(|&ch| !ch.is_control() && !self.chars.contains_key(&(ch as u32)))
+            .collect()
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{semantic_err, BMFontError};
+
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
+
+/// Maps the pages of a font to their images, from the id of each page and the file name written
+/// in the font. The pages are resolved in the order they appear in the font.
+///
+/// Resolvers giving a `PathBuf` can be used when loading a font, for example with
+/// [`BMFont::from_reader_with_resolver`][from_reader], while resolvers giving other handles can
+/// be used with [`BMFont::resolve_pages`][resolve_pages]. Closures taking `(u32, &Path)` become
+/// resolvers with [`resolve_with`][resolve_with].
+///
+/// # Examples
+/// ```
+/// use bmfont_parser::{resolve_with, BMFont, Format, PageResolver};
+/// use std::path::{Path, PathBuf};
+///
+/// /// Looks the textures up in another tree than the fonts.
+/// struct Textures;
+///
+/// impl PageResolver for Textures {
+///     fn resolve(&mut self, _: u32, file: &Path) -> Result<PathBuf, bmfont_parser::BMFontError> {
+///         Ok(Path::new("assets/textures").join(file))
+///     }
+/// }
+///
+/// let iosevka_fnt = include_bytes!("../examples/fonts/iosevka.fnt");
+/// let bmfont = BMFont::from_reader_with_resolver(
+///     &Format::BMFont,
+///     &iosevka_fnt[..],
+///     Textures,
+///     &mut Default::default(),
+/// )
+/// .unwrap();
+/// assert_eq!(bmfont.pages[0].image_path, Path::new("assets/textures/iosevka.png"));
+///
+/// let ids = bmfont.resolve_pages(resolve_with(|id, _| id as usize)).unwrap();
+/// assert_eq!(ids, vec![0]);
+/// ```
+///
+/// [from_reader]: struct.BMFont.html#method.from_reader_with_resolver
+/// [resolve_pages]: struct.BMFont.html#method.resolve_pages
+/// [resolve_with]: fn.resolve_with.html
+pub trait PageResolver<T = PathBuf> {
+    /// Returns the image of the page with the given `id`, whose file name in the font is `file`.
+    fn resolve(&mut self, id: u32, file: &Path) -> Result<T, BMFontError>;
+}
+
+/// A [`PageResolver`][resolver] calling a closure, made with [`resolve_with`][resolve_with].
+///
+/// [resolver]: trait.PageResolver.html
+/// [resolve_with]: fn.resolve_with.html
+#[derive(Debug, Clone, Copy)]
+pub struct ResolveWith<F> {
+    resolve: F,
+}
+
+/// Returns a [`PageResolver`][resolver] giving what `resolve` returns for the id and the file
+/// name of each page.
+///
+/// # Examples
+/// ```
+/// use bmfont_parser::{resolve_with, BMFont, Format};
+///
+/// let bmfont = BMFont::from_path(&Format::BMFont, "examples/fonts/iosevka.fnt").unwrap();
+/// let names = bmfont
+///     .resolve_pages(resolve_with(|_, file| file.file_name().unwrap().to_owned()))
+///     .unwrap();
+/// assert_eq!(names, vec!["iosevka.png"]);
+/// ```
+///
+/// [resolver]: trait.PageResolver.html
+pub fn resolve_with<T, F: FnMut(u32, &Path) -> T>(resolve: F) -> ResolveWith<F> {
+    ResolveWith { resolve }
+}
+
+impl<T, F: FnMut(u32, &Path) -> T> PageResolver<T> for ResolveWith<F> {
+    fn resolve(&mut self, id: u32, file: &Path) -> Result<T, BMFontError> {
+        Ok((self.resolve)(id, file))
+    }
+}
+
+/// Resolves the file names of the pages relative to the directory of the font file, as
+/// [`BMFont::from_path`][from_path] does.
+///
+/// [from_path]: struct.BMFont.html#method.from_path
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelativeToFont {
+    font_path: PathBuf,
+}
+
+impl RelativeToFont {
+    /// Creates a resolver for the font file at `font_path`.
+    pub fn new<T: Into<PathBuf>>(font_path: T) -> RelativeToFont {
+        RelativeToFont {
+            font_path: font_path.into(),
+        }
+    }
+}
+
+impl PageResolver for RelativeToFont {
+    fn resolve(&mut self, _: u32, file: &Path) -> Result<PathBuf, BMFontError> {
+        match self.font_path.parent() {
+            Some(parent) => Ok(parent.join(file)),
+            None => Err(BMFontError::Io(Error::new(
+                ErrorKind::InvalidInput,
+                "Unable to retrieve path parent.",
+            ))),
+        }
+    }
+}
+
+/// Gives the pages the paths of a list, in the order the pages appear in the font, ignoring the
+/// file names in the font. This is what [`BMFont::from_loaded`][from_loaded] does.
+///
+/// [from_loaded]: struct.BMFont.html#method.from_loaded
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExplicitList {
+    paths: Vec<PathBuf>,
+    next: usize,
+}
+
+impl ExplicitList {
+    /// Creates a resolver giving the paths of `paths` in order.
+    pub fn new<T: AsRef<Path>>(paths: &[T]) -> ExplicitList {
+        ExplicitList {
+            paths: paths
+                .iter()
+                .map(|path| path.as_ref().to_path_buf())
+                .collect(),
+            next: 0,
+        }
+    }
+}
+
+impl PageResolver for ExplicitList {
+    fn resolve(&mut self, _: u32, _: &Path) -> Result<PathBuf, BMFontError> {
+        match self.paths.get(self.next) {
+            Some(path) => {
+                self.next += 1;
+                Ok(path.clone())
+            }
+            None => semantic_err("Wrong amount of image paths given to accompany each page"),
+        }
+    }
+}
+
+/// Looks the file names of the pages up in a list of directories, giving the first path that
+/// exists.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchDirectories {
+    directories: Vec<PathBuf>,
+}
+
+impl SearchDirectories {
+    /// Creates a resolver searching `directories` in order.
+    pub fn new<T: AsRef<Path>>(directories: &[T]) -> SearchDirectories {
+        SearchDirectories {
+            directories: directories
+                .iter()
+                .map(|directory| directory.as_ref().to_path_buf())
+                .collect(),
+        }
+    }
+}
+
+impl PageResolver for SearchDirectories {
+    fn resolve(&mut self, id: u32, file: &Path) -> Result<PathBuf, BMFontError> {
+        for directory in self.directories.iter() {
+            let path = directory.join(file);
+            if path.exists() {
+                return Ok(path);
+            }
+        }
+        Err(BMFontError::Io(Error::new(
+            ErrorKind::NotFound,
+            format!(
+                "Could not find the image {} of page {} in the search directories",
+                file.display(),
+                id
+            ),
+        )))
+    }
+}
@@ -1,156 +1,250 @@
+use crate::{BMFontError, Position, SyntaxError};
+
+use std::borrow::Cow;
 use std::str::FromStr;
 
+/// A tokenizer over borrowed text. Everything it returns is a slice of that text, and nothing is
+/// allocated unless an error is built.
 #[derive(Clone)]
-pub(crate) struct Parser {
-    chars: Vec<char>,
+pub(crate) struct Parser<'a> {
+    text: &'a str,
     cursor: usize,
+    mark: Mark,
+}
+
+/// A position in the parsed text, along with the byte index the line starts at.
+#[derive(Clone, Copy, Debug)]
+struct Mark {
+    line: usize,
+    column: usize,
+    line_start: usize,
 }
 
-impl Parser {
-    pub fn new(text: &str) -> Parser {
+impl<'a> Parser<'a> {
+    pub fn new(text: &'a str) -> Parser<'a> {
+        Parser::at_line(text, 1)
+    }
+
+    /// Returns a parser for `text` that starts at the given line of a larger text.
+    pub fn at_line(text: &'a str, line: usize) -> Parser<'a> {
         Parser {
-            chars: text.chars().collect(),
+            text,
             cursor: 0,
+            mark: Mark {
+                line,
+                column: 1,
+                line_start: 0,
+            },
         }
     }
 
-    pub fn peek(&self, amount: usize) -> Option<char> {
-        let idx = self.cursor + amount;
-        if idx >= self.chars.len() {
-            None
-        } else {
-            Some(self.chars[idx])
-        }
+    /// Returns the text that is not consumed yet.
+    fn rest(&self) -> &'a str {
+        &self.text[self.cursor..]
     }
 
-    pub fn expect<T: Into<String>>(&mut self, text: T) -> Expect {
-        Expect::new().or(text, self)
+    pub fn expect(&mut self, text: &'static str) -> Expect<'a> {
+        Expect::new(self).or(text, self)
     }
 
-    pub fn expect_number<T: FromStr>(&mut self) -> Result<T, String> {
-        let mut text = String::new();
-        let mut got_number = Err("No number found".to_owned());
-        while let Some(c) = self.peek(0) {
-            text += &c.to_string();
-            if c == '-' && text.len() == 1 {
-                self.move_cursor(1);
-                continue;
-            }
-            let number = text.parse::<T>();
-            match number {
-                Ok(num) => {
-                    got_number = Ok(num);
-                    self.move_cursor(1);
-                }
-                Err(_) => break,
-            }
+    /// Reads a key followed by `=`, consuming both. Consumes nothing if there is no such key.
+    pub fn expect_key(&mut self) -> Result<&'a str, Unexpected<'a>> {
+        let rest = self.rest();
+        let len = rest.find(|c| !Parser::is_key_char(c)).unwrap_or(rest.len());
+        if len == 0 || rest.as_bytes().get(len) != Some(&b'=') {
+            return Err(self.unexpected(&["key"]));
         }
-        got_number
-    }
-
-    pub fn expect_ident(&mut self) -> Result<String, String> {
-        let mut text = String::new();
-        let mut got_ident = Err("No ident found".to_owned());
-
-        let res = self.expect("\"").get();
-        let within_quotations = res.is_ok();
-
-        while let Some(c) = self.peek(0) {
-            if (!within_quotations && !Parser::is_whitespace(c)) || (within_quotations && c != '\"')
-            {
-                text += &c.to_string();
-                self.move_cursor(1);
-                got_ident = Ok(text.clone());
-            } else if within_quotations && c == '\"' {
-                self.move_cursor(1);
-                got_ident = Ok(text.clone());
-                break;
-            } else {
-                break;
+        self.advance(len + 1);
+        Ok(&rest[..len])
+    }
+
+    /// Reads an optionally negative integer. Consumes nothing if it does not fit in `T`.
+    pub fn expect_number<T: FromStr>(&mut self) -> Result<T, Unexpected<'a>> {
+        let bytes = self.rest().as_bytes();
+        let sign = usize::from(bytes.first() == Some(&b'-'));
+        let digits = bytes[sign..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        if digits == 0 {
+            return Err(self.unexpected(&["number"]));
+        }
+        match self.rest()[..sign + digits].parse() {
+            Ok(number) => {
+                self.advance(sign + digits);
+                Ok(number)
             }
+            Err(_) => Err(self.unexpected(&["number"])),
         }
-        got_ident
     }
 
-    pub fn skip_whitespace(&mut self) {
-        while let Some(c) = self.peek(0) {
-            if Parser::is_whitespace(c) {
-                self.move_cursor(1);
-            } else {
-                break;
+    /// Reads a quoted value, or a bare one up to the next whitespace.
+    pub fn expect_ident(&mut self) -> Result<&'a str, Unexpected<'a>> {
+        let unexpected = self.unexpected(&["ident"]);
+        if self.expect("\"").get().is_ok() {
+            let ident = self.take_while(|c| c != '"');
+            if self.expect("\"").get().is_ok() || !ident.is_empty() {
+                return Ok(ident);
+            }
+        } else {
+            let ident = self.take_while(|c| !Parser::is_whitespace(c));
+            if !ident.is_empty() {
+                return Ok(ident);
             }
         }
+        Err(unexpected)
+    }
+
+    /// Consumes characters as long as `predicate` holds, returning them.
+    pub fn take_while<F: Fn(char) -> bool>(&mut self, predicate: F) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        self.advance(len);
+        &rest[..len]
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        let len = self
+            .rest()
+            .bytes()
+            .take_while(|&b| Parser::is_whitespace(char::from(b)))
+            .count();
+        self.advance(len);
     }
 
     pub fn is_finished(&self) -> bool {
-        self.cursor == self.chars.len()
+        self.cursor == self.text.len()
+    }
+
+    /// Returns the current position.
+    pub fn position(&self) -> Position {
+        Position {
+            line: self.mark.line,
+            column: self.mark.column,
+        }
+    }
+
+    /// Returns a syntax error at the current position.
+    pub fn error<T: Into<String>, U>(&self, text: T) -> Result<U, BMFontError> {
+        Err(self.unexpected(&[]).into_error(text))
     }
 
-    pub fn print_surroundings(&self) {
-        println!("Cursor currently at: {}", self.cursor);
-        let mut text = String::new();
-        for i in 0..30 {
-            if let Some(c) = self.peek(i) {
-                text += &c.to_string();
-            } else {
-                break;
+    fn unexpected(&self, expected: &'static [&'static str]) -> Unexpected<'a> {
+        Unexpected {
+            expected: Cow::Borrowed(expected),
+            text: self.text,
+            mark: self.mark,
+        }
+    }
+
+    /// Moves the cursor `len` bytes forward, which must end on a character boundary.
+    fn advance(&mut self, len: usize) {
+        let end = self.cursor + len;
+        for (idx, &b) in self.text.as_bytes()[self.cursor..end].iter().enumerate() {
+            if b == b'\n' {
+                self.mark.line += 1;
+                self.mark.column = 1;
+                self.mark.line_start = self.cursor + idx + 1;
+            } else if b & 0xC0 != 0x80 {
+                // Only the first byte of each UTF-8 sequence starts a new column.
+                self.mark.column += 1;
             }
         }
-        println!("Next 30 characters, or less: \"{}\"", text);
+        self.cursor = end;
     }
 
-    fn move_cursor(&mut self, amount: usize) {
-        self.cursor += amount;
+    fn is_key_char(c: char) -> bool {
+        c.is_alphanumeric() || c == '_'
     }
 
-    fn is_whitespace(c: char) -> bool {
+    pub fn is_whitespace(c: char) -> bool {
         c == ' ' || c == '\t' || c == '\n' || c == '\r'
     }
 }
 
-pub(crate) struct Expect {
-    previous_expects: Vec<String>,
-    correct: Option<String>,
+pub(crate) struct Expect<'a> {
+    previous_expects: Vec<&'static str>,
+    correct: Option<&'static str>,
+    text: &'a str,
+    mark: Mark,
 }
 
-impl Expect {
-    fn new() -> Expect {
+impl<'a> Expect<'a> {
+    fn new(parser: &Parser<'a>) -> Expect<'a> {
         Expect {
             previous_expects: Vec::new(),
             correct: None,
+            text: parser.text,
+            mark: parser.mark,
         }
     }
 
-    pub(crate) fn or<T: Into<String>>(mut self, expected: T, parser: &mut Parser) -> Expect {
-        let expected = expected.into();
+    pub(crate) fn or(mut self, expected: &'static str, parser: &mut Parser<'a>) -> Expect<'a> {
         if self.correct.is_some() {
-            self
-        } else {
-            for (idx, character) in expected.chars().enumerate() {
-                if let Some(c) = parser.peek(idx) {
-                    if character != c {
-                        self.previous_expects.push(expected);
-                        return self;
-                    }
-                } else {
-                    self.previous_expects.push(expected);
-                    return self;
-                }
-            }
-            parser.move_cursor(expected.chars().count());
+            return self;
+        }
+        let rest = parser.rest();
+        let ends_in_word = expected.chars().last().is_some_and(Parser::is_key_char);
+        let matches = rest.starts_with(expected)
+            && !(ends_in_word
+                && rest[expected.len()..]
+                    .chars()
+                    .next()
+                    .is_some_and(Parser::is_key_char));
+        if matches {
+            parser.advance(expected.len());
             self.correct = Some(expected);
-            self
+        } else {
+            self.previous_expects.push(expected);
         }
+        self
     }
 
-    pub(crate) fn get(&self) -> Result<String, String> {
-        if let Some(correct) = &self.correct {
-            Ok(correct.clone())
-        } else {
-            Err(format!(
-                "Not-expected result, tried: {:?}",
-                self.previous_expects
-            ))
+    pub(crate) fn get(&self) -> Result<&'static str, Unexpected<'a>> {
+        match self.correct {
+            Some(correct) => Ok(correct),
+            None => Err(Unexpected {
+                expected: Cow::Owned(self.previous_expects.clone()),
+                text: self.text,
+                mark: self.mark,
+            }),
         }
     }
 }
+
+/// The parser did not find what it expected.
+#[derive(Debug, Clone)]
+pub(crate) struct Unexpected<'a> {
+    /// The tokens that were tried.
+    pub expected: Cow<'static, [&'static str]>,
+    text: &'a str,
+    mark: Mark,
+}
+
+impl Unexpected<'_> {
+    /// Turns this into a syntax error with the given message, its position and a snippet of the
+    /// line it happened on.
+    pub fn into_error<T: Into<String>>(self, text: T) -> BMFontError {
+        let line = &self.text[self.mark.line_start..];
+        let line = &line[..line.find(['\n', '\r']).unwrap_or(line.len())];
+        let caret: String = line
+            .chars()
+            .take(self.mark.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let number = self.mark.line.to_string();
+        let gutter = " ".repeat(number.len());
+        BMFontError::Syntax(SyntaxError {
+            message: text.into(),
+            expected: self.expected.iter().map(|&e| e.to_owned()).collect(),
+            position: Some(Position {
+                line: self.mark.line,
+                column: self.mark.column,
+            }),
+            snippet: Some(format!(
+                "{} |\n{} | {}\n{} | {}^",
+                gutter, number, line, gutter, caret
+            )),
+        })
+    }
+}
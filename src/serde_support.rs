@@ -0,0 +1,68 @@
+//! Serde representations of the chars and kernings of a font, which are written as sequences
+//! sorted by id. This keeps the output the same between runs, and works with formats whose maps
+//! only take string keys, like JSON.
+//!
+//! Every type deriving the serde traits names its fields in snake_case and its enum variants in
+//! PascalCase, the same as in Rust, e.g. `{"font_name": ..}` and `"BMFontXml"`. The scheme is
+//! spelled out with `rename_all` on each of them, and new types should do the same.
+
+pub(crate) mod chars {
+    use crate::BMCharacter;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::collections::HashMap;
+
+    pub(crate) fn serialize<S: Serializer>(
+        chars: &HashMap<u32, BMCharacter>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut chars: Vec<_> = chars.values().collect();
+        chars.sort_by_key(|c| c.id);
+        serializer.collect_seq(chars)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<u32, BMCharacter>, D::Error> {
+        let chars = Vec::<BMCharacter>::deserialize(deserializer)?;
+        Ok(chars.into_iter().map(|c| (c.id, c)).collect())
+    }
+}
+
+pub(crate) mod kernings {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    struct Kerning {
+        first: u32,
+        second: u32,
+        amount: i32,
+    }
+
+    pub(crate) fn serialize<S: Serializer>(
+        kernings: &HashMap<(u32, u32), i32>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut kernings: Vec<_> = kernings
+            .iter()
+            .map(|(&(first, second), &amount)| Kerning {
+                first,
+                second,
+                amount,
+            })
+            .collect();
+        kernings.sort_by_key(|k| (k.first, k.second));
+        serializer.collect_seq(kernings)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<(u32, u32), i32>, D::Error> {
+        let kernings = Vec::<Kerning>::deserialize(deserializer)?;
+        Ok(kernings
+            .into_iter()
+            .map(|k| ((k.first, k.second), k.amount))
+            .collect())
+    }
+}
@@ -1,34 +1,35 @@
 use crate::parser::Parser;
-use crate::{err, result_or};
-use crate::{BMCharacter, BMFont, Page};
+use crate::{expect_or, BMFontError, ParseOptions};
+use crate::{BMCharacter, BMFontRef, DeclaredCounts, PageRef};
 
-use std::collections::HashMap;
-use std::io::Error;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
 
-pub(crate) fn load<T: Into<String>>(sfl_contents: T) -> Result<BMFont, Error> {
-    let content = sfl_contents.into();
-    let mut parser = Parser::new(&content);
+pub(crate) fn load<'a>(
+    content: &'a str,
+    options: &mut ParseOptions,
+) -> Result<BMFontRef<'a>, BMFontError> {
+    let mut parser = Parser::new(content);
 
     parser.skip_whitespace();
-    let font_name = result_or(parser.expect_ident(), "Could not parse font name")?;
+    let font_name = expect_or(parser.expect_ident(), "Could not parse font name")?;
 
     parser.skip_whitespace();
-    let size = result_or(parser.expect_number(), "Could not parse font size")?;
+    let size = expect_or(parser.expect_number(), "Could not parse font size")?;
     parser.skip_whitespace();
-    let line_height = result_or(parser.expect_number(), "Could not parse font height")?;
+    let line_height = expect_or(parser.expect_number(), "Could not parse font height")?;
 
     parser.skip_whitespace();
-    let image_name = result_or(parser.expect_ident(), "Could not parse image path")?;
-    let mut image_path = PathBuf::new();
-    image_path.push(image_name);
+    let image_path = expect_or(parser.expect_ident(), "Could not parse image path")?;
 
     parser.skip_whitespace();
-    let char_amount = result_or(parser.expect_number(), "Could not parse character amount")?;
+    let char_amount = expect_or(parser.expect_number(), "Could not parse character amount")?;
 
     let mut chars = HashMap::<u32, BMCharacter>::new();
+    let mut declared_counts = DeclaredCounts::default();
     for idx in 0..char_amount {
+        parser.skip_whitespace();
+        let position = parser.position();
         let char_id = parse_charpart(&mut parser, "character id", idx)?;
         let x = parse_charpart(&mut parser, "x", char_id)?;
         let y = parse_charpart(&mut parser, "y", char_id)?;
@@ -38,7 +39,7 @@ pub(crate) fn load<T: Into<String>>(sfl_contents: T) -> Result<BMFont, Error> {
         let yoffset = parse_charpart(&mut parser, "yoffset", char_id)?;
         let xadvance = parse_charpart(&mut parser, "xadvance", char_id)?;
 
-        chars.insert(
+        let old = chars.insert(
             char_id,
             BMCharacter {
                 id: char_id,
@@ -51,30 +52,35 @@ pub(crate) fn load<T: Into<String>>(sfl_contents: T) -> Result<BMFont, Error> {
                 xadvance,
                 page: 0,
                 channel: 15,
+                extra: BTreeMap::new(),
             },
         );
+        if old.is_some() {
+            declared_counts.duplicate_char(char_id, Some(position), options);
+        }
     }
 
     parser.skip_whitespace();
-    parser
-        .expect_number::<u32>()
-        .expect("Could not find the ending-0");
+    expect_or(parser.expect_number::<u32>(), "Could not find the ending-0")?;
     parser.skip_whitespace();
 
     if !parser.is_finished() {
-        err("Unnecessary text after ending-0")
+        parser.error("Unnecessary text after ending-0")
     } else {
-        Ok(BMFont {
+        Ok(BMFontRef {
             font_name,
             size,
             info_details: None,
             line_height,
             common_details: None,
-            pages: vec![Page {
+            pages: vec![PageRef {
                 id: 0,
-                image_path: image_path,
+                image_path,
+                extra: BTreeMap::new(),
             }],
             chars,
+            kernings: HashMap::new(),
+            declared_counts,
         })
     }
 }
@@ -83,9 +89,9 @@ pub(crate) fn parse_charpart<T: FromStr>(
     parser: &mut Parser,
     part: &str,
     id: u32,
-) -> Result<T, Error> {
+) -> Result<T, BMFontError> {
     parser.skip_whitespace();
-    result_or(
+    expect_or(
         parser.expect_number(),
         format!("Could not parse {} of char {}", part, id),
     )
@@ -0,0 +1,59 @@
+use crate::{unsupported_err, BMFont, BMFontError};
+
+use std::io::Write;
+
+pub(crate) fn write<W: Write>(bmfont: &BMFont, mut w: W) -> Result<(), BMFontError> {
+    let page = match bmfont.pages.as_slice() {
+        [page] => page,
+        _ => {
+            return unsupported_err(format!(
+                "Unable to write {} pages into an .sfl file, which only supports one",
+                bmfont.pages.len()
+            ))
+        }
+    };
+    if !bmfont.kernings.is_empty() {
+        return unsupported_err("Unable to write kernings into an .sfl file");
+    }
+
+    let mut chars: Vec<_> = bmfont.chars.values().collect();
+    chars.sort_by_key(|c| c.id);
+    for c in chars.iter() {
+        if c.page != page.id {
+            return unsupported_err(format!(
+                "Unable to write char {} on page {} into an .sfl file, which only has page {}",
+                c.id, c.page, page.id
+            ));
+        }
+        if c.channel != 15 {
+            return unsupported_err(format!(
+                "Unable to write char {} with channel {} into an .sfl file, which only supports channel 15",
+                c.id, c.channel
+            ));
+        }
+    }
+
+    writeln!(w, "{}", ident(&bmfont.font_name))?;
+    writeln!(w, "{} {}", bmfont.size, bmfont.line_height)?;
+    writeln!(w, "{}", ident(&page.image_path.to_string_lossy()))?;
+    writeln!(w, "{}", chars.len())?;
+    for c in chars {
+        writeln!(
+            w,
+            "{} {} {} {} {} {} {} {} ",
+            c.id, c.x, c.y, c.width, c.height, c.xoffset, c.yoffset, c.xadvance
+        )?;
+    }
+    writeln!(w, "0")?;
+
+    Ok(())
+}
+
+/// Quotes `text` if it contains whitespace, so that it can be read back as a single ident.
+fn ident(text: &str) -> String {
+    if text.contains(char::is_whitespace) {
+        format!("\"{}\"", text)
+    } else {
+        text.to_owned()
+    }
+}
@@ -0,0 +1,100 @@
+use super::{from_bytes_setup_binary, from_path_setup_bmfont};
+use crate::{BMFont, Format};
+
+fn block(block_type: u8, contents: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![block_type];
+    bytes.extend_from_slice(&(contents.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(contents);
+    bytes
+}
+
+fn kerned_binary() -> Vec<u8> {
+    let mut bytes = b"BMF\x03".to_vec();
+    let mut info = vec![0xD0, 0xFF, 0xE0, 0, 100, 0, 1, 1, 2, 3, 4, 5, 6, 7];
+    info.extend_from_slice(b"Test\0");
+    bytes.extend(block(1, &info));
+    bytes.extend(block(
+        2,
+        &[32, 0, 26, 0, 0, 1, 128, 0, 1, 0, 0x01, 1, 0, 0, 0],
+    ));
+    bytes.extend(block(3, b"test.png\0"));
+    let mut chars = Vec::new();
+    for id in [65u32, 86].iter() {
+        chars.extend_from_slice(&id.to_le_bytes());
+        chars.extend_from_slice(&[1, 0, 2, 0, 10, 0, 12, 0, 0xFF, 0xFF, 3, 0, 9, 0, 0, 4]);
+    }
+    bytes.extend(block(4, &chars));
+    let mut kernings = Vec::new();
+    kernings.extend_from_slice(&65u32.to_le_bytes());
+    kernings.extend_from_slice(&86u32.to_le_bytes());
+    kernings.extend_from_slice(&(-2i16).to_le_bytes());
+    bytes.extend(block(5, &kernings));
+    bytes
+}
+
+#[test]
+fn matches_text_format() {
+    let binary = from_bytes_setup_binary();
+    let text = from_path_setup_bmfont();
+    for (id, c) in text.chars.iter() {
+        let b = &binary.chars[id];
+        assert_eq!(
+            (b.x, b.y, b.width, b.height, b.xoffset, b.yoffset, b.xadvance),
+            (c.x, c.y, c.width, c.height, c.xoffset, c.yoffset, c.xadvance)
+        );
+    }
+    let common = binary.common_details.unwrap();
+    assert_eq!(common.base, 42);
+    assert_eq!((common.scale_w, common.scale_h), (1024, 512));
+    assert_eq!(binary.info_details.unwrap().smooth, 1);
+}
+
+#[test]
+fn info_bitfield() {
+    let font = BMFont::from_bytes(&Format::BMFontBinary, &kerned_binary(), &["test.png"]).unwrap();
+    let info = font.info_details.unwrap();
+    assert_eq!(font.font_name, "Test");
+    assert_eq!(font.size, 48);
+    assert_eq!(
+        (info.smooth, info.unicode, info.italic, info.bold),
+        (1, 1, 1, 0)
+    );
+    assert_eq!(info.charset, "");
+    assert_eq!(info.stretch_h, 100);
+    assert_eq!(info.padding, [1, 2, 3, 4]);
+    assert_eq!(info.spacing, [5, 6]);
+    assert_eq!(info.outline, 7);
+}
+
+#[test]
+fn common_bitfield() {
+    let font = BMFont::from_bytes(&Format::BMFontBinary, &kerned_binary(), &["test.png"]).unwrap();
+    let common = font.common_details.unwrap();
+    assert_eq!(font.line_height, 32);
+    assert_eq!(common.packed, 1);
+    assert_eq!(common.alpha_channel, 1);
+    assert_eq!(common.pages_count, 1);
+}
+
+#[test]
+fn chars_and_kernings() {
+    let font = BMFont::from_bytes(&Format::BMFontBinary, &kerned_binary(), &["test.png"]).unwrap();
+    assert_eq!(font.chars.len(), 2);
+    assert_eq!(font.chars[&86].xoffset, -1);
+    assert_eq!(font.chars[&86].channel, 4);
+    assert_eq!(font.kerning(65, 86), -2);
+}
+
+#[test]
+fn unsupported_version() {
+    let mut bytes = kerned_binary();
+    bytes[3] = 2;
+    assert!(BMFont::from_bytes(&Format::BMFontBinary, &bytes, &["test.png"]).is_err());
+}
+
+#[test]
+fn truncated_block() {
+    let bytes = kerned_binary();
+    let truncated = &bytes[..bytes.len() - 3];
+    assert!(BMFont::from_bytes(&Format::BMFontBinary, truncated, &["test.png"]).is_err());
+}
@@ -0,0 +1,82 @@
+use super::for_each_font;
+use super::kerning::kerned_font;
+use crate::{BMFont, Format, Page};
+
+use std::collections::BTreeMap;
+
+fn rewrite(font: &BMFont) -> BMFont {
+    let mut fnt = Vec::new();
+    font.write_binary(&mut fnt).unwrap();
+    let image_paths: Vec<String> = font
+        .pages
+        .iter()
+        .map(|page| page.image_path.to_string_lossy().into_owned())
+        .collect();
+    let image_paths: Vec<&str> = image_paths.iter().map(|path| path.as_str()).collect();
+    match BMFont::from_bytes(&Format::BMFontBinary, &fnt, &image_paths) {
+        Ok(bmfont) => bmfont,
+        Err(e) => panic!("Failed to load written font: {}", e),
+    }
+}
+
+#[test]
+fn round_trip() {
+    for_each_font(|font| {
+        let written = rewrite(font);
+        assert_eq!(written.font_name, font.font_name);
+        assert_eq!(written.size, font.size);
+        assert_eq!(written.line_height, font.line_height);
+        assert_eq!(written.pages, font.pages);
+        assert_eq!(written.chars, font.chars);
+        if font.common_details.is_some() {
+            assert_eq!(written.common_details, font.common_details);
+        }
+    });
+}
+
+#[test]
+fn round_trip_kernings_and_flags() {
+    let mut font = kerned_font();
+    {
+        let info = font.info_details.as_mut().unwrap();
+        info.bold = 1;
+        info.italic = 1;
+        info.padding = [1, 2, 3, 4];
+        info.outline = 2;
+    }
+    font.common_details.as_mut().unwrap().packed = 1;
+    assert_eq!(rewrite(&font), font);
+}
+
+#[test]
+fn same_bytes() {
+    // The writer sorts the chars by id, which BMFont itself does not, so its output is kept in a
+    // fixture of its own.
+    let original = include_bytes!("../../examples/fonts/iosevka-binary.fnt");
+    let expected = include_bytes!("../../examples/fonts/iosevka-binary-written.fnt");
+    let font = BMFont::from_bytes(&Format::BMFontBinary, original, &["iosevka.png"]).unwrap();
+    let mut written = Vec::new();
+    font.write_binary(&mut written).unwrap();
+    assert_eq!(&written[..], &expected[..]);
+
+    let rewritten = BMFont::from_bytes(&Format::BMFontBinary, expected, &["iosevka.png"]).unwrap();
+    assert_eq!(rewritten, font);
+}
+
+#[test]
+fn value_out_of_range() {
+    let mut font = kerned_font();
+    font.chars.get_mut(&65).unwrap().x = 70000;
+    assert!(font.write_binary(Vec::new()).is_err());
+}
+
+#[test]
+fn page_names_of_different_lengths() {
+    let mut font = kerned_font();
+    font.pages.push(Page {
+        id: 1,
+        image_path: "arial_1.png".into(),
+        extra: BTreeMap::new(),
+    });
+    assert!(font.write_binary(Vec::new()).is_err());
+}
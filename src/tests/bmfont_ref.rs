@@ -0,0 +1,53 @@
+use super::{from_loaded_setup_bmfont, from_loaded_setup_sfl};
+use crate::{BMFontError, BMFontRef, Format, ParseOptions};
+
+use std::path::PathBuf;
+
+#[test]
+fn borrows_names() {
+    let iosevka_fnt = include_str!("../../examples/fonts/iosevka.fnt");
+    let font = BMFontRef::parse(&Format::BMFont, iosevka_fnt).unwrap();
+    let name = font.font_name.as_ptr() as usize;
+    let text = iosevka_fnt.as_ptr() as usize..iosevka_fnt.as_ptr() as usize + iosevka_fnt.len();
+    assert!(text.contains(&name));
+    assert_eq!(font.pages[0].image_path, "iosevka.png");
+}
+
+#[test]
+fn to_owned_matches_bmfont() {
+    let iosevka_fnt = include_str!("../../examples/fonts/iosevka.fnt");
+    let mut owned = BMFontRef::parse(&Format::BMFont, iosevka_fnt)
+        .unwrap()
+        .to_owned();
+    owned.pages[0].image_path = PathBuf::from("examples/fonts/iosevka.png");
+    assert_eq!(owned, from_loaded_setup_bmfont());
+
+    let iosevka_sfl = include_str!("../../examples/fonts/iosevka.sfl");
+    let mut owned = BMFontRef::parse(&Format::SFL, iosevka_sfl)
+        .unwrap()
+        .to_owned();
+    owned.pages[0].image_path = PathBuf::from("examples/fonts/iosevka.png");
+    assert_eq!(owned, from_loaded_setup_sfl());
+}
+
+#[test]
+fn lenient_keeps_extra() {
+    let iosevka_fnt = include_str!("../../examples/fonts/iosevka.fnt");
+    let fnt = iosevka_fnt.replacen(" spacing=", " letterSpacing=2 spacing=", 1);
+    let mut options = ParseOptions {
+        strict: false,
+        ..ParseOptions::default()
+    };
+    let font = BMFontRef::parse_with(&Format::BMFont, &fnt, &mut options).unwrap();
+    let extra = &font.info_details.unwrap().extra;
+    assert_eq!(extra.get("letterSpacing").map(String::as_str), Some("2"));
+}
+
+#[test]
+fn other_formats_are_unsupported() {
+    let iosevka_xml = include_str!("../../examples/fonts/iosevka.xml");
+    match BMFontRef::parse(&Format::BMFontXml, iosevka_xml) {
+        Err(BMFontError::Unsupported(_)) => (),
+        other => panic!("Expected an Unsupported error, got {:?}", other),
+    }
+}
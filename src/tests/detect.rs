@@ -0,0 +1,78 @@
+use crate::{BMFont, Format};
+
+#[test]
+fn detect_fixtures() {
+    let fixtures: [(&[u8], Format); 5] = [
+        (
+            include_bytes!("../../examples/fonts/iosevka.sfl"),
+            Format::SFL,
+        ),
+        (
+            include_bytes!("../../examples/fonts/iosevka.fnt"),
+            Format::BMFont,
+        ),
+        (
+            include_bytes!("../../examples/fonts/iosevka-binary.fnt"),
+            Format::BMFontBinary,
+        ),
+        (
+            include_bytes!("../../examples/fonts/iosevka.xml"),
+            Format::BMFontXml,
+        ),
+        (
+            include_bytes!("../../examples/fonts/iosevka.json"),
+            Format::BMFontJson,
+        ),
+    ];
+    for (contents, format) in fixtures.iter() {
+        assert_eq!(Format::detect(contents), Some(*format));
+    }
+}
+
+#[test]
+fn detect_with_bom_and_whitespace() {
+    assert_eq!(
+        Format::detect(b"\xEF\xBB\xBF\r\n  info face=\"A\""),
+        Some(Format::BMFont)
+    );
+    assert_eq!(
+        Format::detect(b"\n<font>\n</font>"),
+        Some(Format::BMFontXml)
+    );
+    assert_eq!(
+        Format::detect(b"  {\"pages\": []}"),
+        Some(Format::BMFontJson)
+    );
+}
+
+#[test]
+fn detect_unknown() {
+    assert_eq!(Format::detect(b""), None);
+    assert_eq!(Format::detect(b"Iosevka\nthirty two\nimage.png\n1\n"), None);
+    assert_eq!(Format::detect(b"\x89PNG\r\n"), None);
+}
+
+#[test]
+fn from_path_auto() {
+    for path in [
+        "examples/fonts/iosevka.sfl",
+        "examples/fonts/iosevka.fnt",
+        "examples/fonts/iosevka-binary.fnt",
+        "examples/fonts/iosevka.xml",
+        "examples/fonts/iosevka.json",
+    ]
+    .iter()
+    {
+        let font = BMFont::from_path_auto(*path).unwrap();
+        assert_eq!(font.chars.len(), 486);
+        assert_eq!(BMFont::load_auto(*path).unwrap(), font);
+    }
+}
+
+#[test]
+fn from_bytes_auto() {
+    let iosevka_xml = include_bytes!("../../examples/fonts/iosevka.xml");
+    let font = BMFont::from_bytes_auto(iosevka_xml, &["iosevka.png"]).unwrap();
+    assert_eq!(font.font_name, "Iosevka");
+    assert!(BMFont::from_bytes_auto(b"not a font", &[]).is_err());
+}
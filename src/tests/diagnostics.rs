@@ -0,0 +1,95 @@
+use crate::{BMFont, Diagnostic, DiagnosticLevel, Format, ParseOptions, Position};
+
+fn load_fnt(contents: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut options = ParseOptions::with_diagnostics(&mut diagnostics);
+    BMFont::from_bytes_with(
+        &Format::BMFont,
+        contents.as_bytes(),
+        &["a.png"],
+        &mut options,
+    )
+    .unwrap();
+    diagnostics
+}
+
+fn warnings(diagnostics: &[Diagnostic]) -> Vec<&Diagnostic> {
+    diagnostics
+        .iter()
+        .filter(|d| d.level == DiagnosticLevel::Warning)
+        .collect()
+}
+
+#[test]
+fn fixtures_have_no_warnings() {
+    let fixtures: [(&[u8], Format); 5] = [
+        (
+            include_bytes!("../../examples/fonts/iosevka.sfl"),
+            Format::SFL,
+        ),
+        (
+            include_bytes!("../../examples/fonts/iosevka.fnt"),
+            Format::BMFont,
+        ),
+        (
+            include_bytes!("../../examples/fonts/iosevka-binary.fnt"),
+            Format::BMFontBinary,
+        ),
+        (
+            include_bytes!("../../examples/fonts/iosevka.xml"),
+            Format::BMFontXml,
+        ),
+        (
+            include_bytes!("../../examples/fonts/iosevka.json"),
+            Format::BMFontJson,
+        ),
+    ];
+    for (contents, format) in fixtures.iter() {
+        let mut diagnostics = Vec::new();
+        let mut options = ParseOptions::with_diagnostics(&mut diagnostics);
+        BMFont::from_bytes_with(format, contents, &["a.png"], &mut options).unwrap();
+        assert!(warnings(&diagnostics).is_empty(), "{:?}", diagnostics);
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.level == DiagnosticLevel::Debug));
+    }
+}
+
+#[test]
+fn wrong_chars_count() {
+    let iosevka_fnt = include_str!("../../examples/fonts/iosevka.fnt");
+    let broken = iosevka_fnt.replacen("char id=", "chars count=1\nchar id=", 1);
+    let line = broken
+        .lines()
+        .position(|line| line.starts_with("chars count="))
+        .unwrap()
+        + 1;
+    let diagnostics = load_fnt(&broken);
+    let warnings = warnings(&diagnostics);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.starts_with("chars count is 1"));
+    assert_eq!(warnings[0].position, Some(Position { line, column: 1 }));
+}
+
+#[test]
+fn duplicate_char() {
+    let iosevka_fnt = include_str!("../../examples/fonts/iosevka.fnt");
+    let broken = iosevka_fnt.replacen("char id=33 ", "char id=32 ", 1);
+    let diagnostics = load_fnt(&broken);
+    assert!(warnings(&diagnostics)
+        .iter()
+        .any(|d| d.message.starts_with("Duplicate char id 32")));
+}
+
+#[test]
+fn closure_sink() {
+    let mut count = 0;
+    let mut sink = |_: Diagnostic| count += 1;
+    BMFont::from_path_with(
+        &Format::SFL,
+        "examples/fonts/iosevka.sfl",
+        &mut ParseOptions::with_diagnostics(&mut sink),
+    )
+    .unwrap();
+    assert!(count > 0);
+}
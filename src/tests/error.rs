@@ -0,0 +1,105 @@
+use super::kerning::kerned_font;
+use crate::{BMFont, BMFontError, Format, Position};
+
+#[test]
+fn missing_file_is_io() {
+    match BMFont::from_path(&Format::BMFont, "examples/fonts/missing.fnt") {
+        Err(BMFontError::Io(_)) => (),
+        other => panic!("Expected an Io error, got {:?}", other),
+    }
+}
+
+#[test]
+fn syntax_error_lists_expected() {
+    match BMFont::from_loaded(&Format::BMFont, "common lineHeight=10", &["a.png"]) {
+        Err(BMFontError::Syntax(error)) => assert_eq!(error.expected, vec!["info"]),
+        other => panic!("Expected a Syntax error, got {:?}", other),
+    }
+}
+
+#[test]
+fn wrong_path_count_is_semantic() {
+    let iosevka_fnt = include_str!("../../examples/fonts/iosevka.fnt");
+    match BMFont::from_loaded(&Format::BMFont, iosevka_fnt, &[]) {
+        Err(BMFontError::Semantic(_)) => (),
+        other => panic!("Expected a Semantic error, got {:?}", other),
+    }
+}
+
+#[test]
+fn sfl_with_kernings_is_unsupported() {
+    match kerned_font().write_sfl(Vec::new()) {
+        Err(BMFontError::Unsupported(_)) => (),
+        other => panic!("Expected an Unsupported error, got {:?}", other),
+    }
+}
+
+#[test]
+fn unknown_binary_version_is_unsupported() {
+    match BMFont::from_bytes(&Format::BMFontBinary, b"BMF\x02", &["a.png"]) {
+        Err(BMFontError::Unsupported(_)) => (),
+        other => panic!("Expected an Unsupported error, got {:?}", other),
+    }
+}
+
+#[test]
+fn missing_sfl_ending_is_an_error() {
+    let iosevka_sfl = include_str!("../../examples/fonts/iosevka.sfl");
+    let truncated = iosevka_sfl.trim_end().trim_end_matches('0');
+    assert!(BMFont::from_loaded(&Format::SFL, truncated, &["a.png"]).is_err());
+}
+
+#[test]
+fn syntax_error_has_position_and_snippet() {
+    let iosevka_fnt = include_str!("../../examples/fonts/iosevka.fnt");
+    let broken = iosevka_fnt.replacen("char id=33 x=", "char id=33 x=abc", 1);
+    let line = broken
+        .lines()
+        .position(|line| line.starts_with("char id=33 "))
+        .unwrap()
+        + 1;
+    match BMFont::from_loaded(&Format::BMFont, broken, &["a.png"]) {
+        Err(BMFontError::Syntax(error)) => {
+            assert_eq!(error.position, Some(Position { line, column: 14 }));
+            let snippet = error.snippet.unwrap();
+            let lines: Vec<&str> = snippet.lines().collect();
+            assert!(lines[1].contains("| char id=33 x=abc233 "));
+            let caret = lines[2].find('^').unwrap() - lines[2].find('|').unwrap() - 2;
+            assert_eq!(caret, "char id=33 x=".len());
+        }
+        other => panic!("Expected a Syntax error, got {:?}", other),
+    }
+}
+
+#[test]
+fn sfl_syntax_error_has_position() {
+    let iosevka_sfl = include_str!("../../examples/fonts/iosevka.sfl");
+    let broken = iosevka_sfl.replacen("\n32 233 ", "\n32 x233 ", 1);
+    match BMFont::from_loaded(&Format::SFL, broken, &["a.png"]) {
+        Err(BMFontError::Syntax(error)) => {
+            assert_eq!(error.position.map(|p| p.column), Some(4));
+            assert!(error.to_string().contains("^"));
+        }
+        other => panic!("Expected a Syntax error, got {:?}", other),
+    }
+}
+
+#[test]
+fn columns_count_characters() {
+    let iosevka_fnt = include_str!("../../examples/fonts/iosevka.fnt");
+    let broken = iosevka_fnt.replacen("face=\"Iosevka\" size=32", "face=\"Ιοσέβκα\" size=x", 1);
+    match BMFont::from_loaded(&Format::BMFont, broken, &["a.png"]) {
+        Err(BMFontError::Syntax(error)) => {
+            let column = "info face=\"Ιοσέβκα\" size=".chars().count() + 1;
+            assert_eq!(error.position, Some(Position { line: 1, column }));
+        }
+        other => panic!("Expected a Syntax error, got {:?}", other),
+    }
+}
+
+#[test]
+fn overflowing_number_is_an_error() {
+    let iosevka_fnt = include_str!("../../examples/fonts/iosevka.fnt");
+    let broken = iosevka_fnt.replacen("char id=33 ", "char id=4294967296 ", 1);
+    assert!(BMFont::from_loaded(&Format::BMFont, broken, &["a.png"]).is_err());
+}
@@ -0,0 +1,138 @@
+use super::kerning::kerned_font;
+use crate::{BMFont, FntDocument, Format, ParseOptions};
+
+const HAND_WRITTEN: &str = "info face=Iosevka size=32  bold=0 letterSpacing=2\r
+common lineHeight=53 base=42\r
+page id=0 file=\"iosevka.png\"\r
+chars count=2\r
+char id=33   x=1 y=2 width=3 height=4 xoffset=0 yoffset=0 xadvance=5 page=0 chnl=15\r
+char id=32   x=0 y=0 width=0 height=0 xoffset=0 yoffset=0 xadvance=5 page=0 chnl=15\r
+kernings count=1\r
+kerning first=32 second=33 amount=-1";
+
+fn changed_lines(before: &str, after: &str) -> Vec<(String, String)> {
+    assert_eq!(before.lines().count(), after.lines().count());
+    before
+        .lines()
+        .zip(after.lines())
+        .filter(|(before, after)| before != after)
+        .map(|(before, after)| (before.to_owned(), after.to_owned()))
+        .collect()
+}
+
+#[test]
+fn unchanged_round_trip() {
+    let iosevka_fnt = include_str!("../../examples/fonts/iosevka.fnt");
+    let mut document = FntDocument::parse(iosevka_fnt).unwrap();
+    assert_eq!(document.to_string(), iosevka_fnt);
+
+    let font = document.to_bmfont().unwrap();
+    document.update_from(&font);
+    assert_eq!(document.to_string(), iosevka_fnt);
+
+    let mut document = FntDocument::parse(HAND_WRITTEN).unwrap();
+    assert_eq!(document.to_string(), HAND_WRITTEN);
+    let font = document.to_bmfont().unwrap();
+    document.update_from(&font);
+    assert_eq!(document.to_string(), HAND_WRITTEN);
+}
+
+#[test]
+fn to_bmfont() {
+    let iosevka_fnt = include_str!("../../examples/fonts/iosevka.fnt");
+    let document = FntDocument::parse(iosevka_fnt).unwrap();
+    let font = BMFont::from_loaded(&Format::BMFont, iosevka_fnt, &["iosevka.png"]).unwrap();
+    assert_eq!(document.to_bmfont().unwrap(), font);
+
+    // Unknown keys are kept, unless strict parsing is asked for.
+    let document = FntDocument::parse(HAND_WRITTEN).unwrap();
+    let font = document.to_bmfont().unwrap();
+    assert_eq!(font.info_details.unwrap().extra["letterSpacing"], "2");
+    assert!(document
+        .to_bmfont_with(&mut ParseOptions::default())
+        .is_err());
+}
+
+#[test]
+fn edits_touch_only_their_keys() {
+    let mut document = FntDocument::parse(HAND_WRITTEN).unwrap();
+    let mut font = document.to_bmfont().unwrap();
+    font.chars.get_mut(&33).unwrap().xadvance = 6;
+    font.info_details.as_mut().unwrap().bold = 1;
+    font.info_details
+        .as_mut()
+        .unwrap()
+        .extra
+        .insert("letterSpacing".to_owned(), "3".to_owned());
+    font.font_name = "Iosevka Term".to_owned();
+    document.update_from(&font);
+
+    let written = document.to_string();
+    assert_eq!(
+        changed_lines(HAND_WRITTEN, &written),
+        vec![
+            (
+                "info face=Iosevka size=32  bold=0 letterSpacing=2".to_owned(),
+                "info face=\"Iosevka Term\" size=32  bold=1 letterSpacing=3".to_owned()
+            ),
+            (
+                "char id=33   x=1 y=2 width=3 height=4 xoffset=0 yoffset=0 xadvance=5 page=0 chnl=15"
+                    .to_owned(),
+                "char id=33   x=1 y=2 width=3 height=4 xoffset=0 yoffset=0 xadvance=6 page=0 chnl=15"
+                    .to_owned()
+            ),
+        ]
+    );
+    assert_eq!(document.to_bmfont().unwrap(), font);
+}
+
+#[test]
+fn missing_keys_are_added_when_needed() {
+    let mut document = FntDocument::parse(HAND_WRITTEN).unwrap();
+    let mut font = document.to_bmfont().unwrap();
+    font.info_details.as_mut().unwrap().extra.clear();
+    font.info_details.as_mut().unwrap().outline = 2;
+    document.update_from(&font);
+    assert!(document
+        .to_string()
+        .starts_with("info face=Iosevka size=32  bold=0 outline=2\r\n"));
+}
+
+#[test]
+fn added_and_removed_lines() {
+    let mut document = FntDocument::parse(HAND_WRITTEN).unwrap();
+    let mut font = document.to_bmfont().unwrap();
+    let mut c = font.chars.remove(&33).unwrap();
+    c.id = 34;
+    font.chars.insert(34, c);
+    font.kernings.clear();
+    font.kernings.insert((34, 32), 2);
+    document.update_from(&font);
+
+    let written = document.to_string();
+    let lines: Vec<&str> = written.lines().collect();
+    assert_eq!(lines[3], "chars count=2");
+    assert!(lines[4].starts_with("char id=32 "));
+    assert_eq!(
+        lines[5],
+        "char id=34 x=1 y=2 width=3 height=4 xoffset=0 yoffset=0 xadvance=5 page=0 chnl=15"
+    );
+    assert_eq!(lines[7], "kerning first=34 second=32 amount=2");
+    assert_eq!(lines.len(), 8);
+    assert!(!written.replace("\r\n", "").contains('\n'));
+    assert!(!written.ends_with('\n'));
+    assert_eq!(document.to_bmfont().unwrap(), font);
+}
+
+#[test]
+fn from_bmfont() {
+    let font = kerned_font();
+    let mut fnt = Vec::new();
+    font.write_fnt(&mut fnt).unwrap();
+    let document = FntDocument::from_bmfont(&font).unwrap();
+    assert_eq!(document.to_string().as_bytes(), fnt.as_slice());
+
+    let mut empty = FntDocument::parse("").unwrap();
+    empty.update_from(&font);
+    assert_eq!(empty.to_bmfont().unwrap().chars, font.chars);
+}
@@ -0,0 +1,88 @@
+use super::for_each_font;
+use super::kerning::kerned_font;
+use crate::{BMFont, BMFontError, Format};
+
+fn rewrite(font: &BMFont) -> BMFont {
+    let mut fnt = Vec::new();
+    font.write_fnt(&mut fnt).unwrap();
+    let image_paths: Vec<String> = font
+        .pages
+        .iter()
+        .map(|page| page.image_path.to_string_lossy().into_owned())
+        .collect();
+    let image_paths: Vec<&str> = image_paths.iter().map(|path| path.as_str()).collect();
+    match BMFont::from_bytes(&Format::BMFont, &fnt, &image_paths) {
+        Ok(bmfont) => bmfont,
+        Err(e) => panic!("Failed to load written font: {}", e),
+    }
+}
+
+#[test]
+fn round_trip() {
+    for_each_font(|font| {
+        let written = rewrite(font);
+        assert_eq!(written.font_name, font.font_name);
+        assert_eq!(written.size, font.size);
+        assert_eq!(written.line_height, font.line_height);
+        assert_eq!(written.pages, font.pages);
+        assert_eq!(written.chars, font.chars);
+        if font.info_details.is_some() {
+            assert_eq!(written.info_details, font.info_details);
+            assert_eq!(written.common_details, font.common_details);
+        }
+    });
+}
+
+#[test]
+fn round_trip_kernings() {
+    let font = kerned_font();
+    assert_eq!(rewrite(&font), font);
+}
+
+#[test]
+fn sfl_defaults() {
+    let font = BMFont::from_path(&Format::SFL, "examples/fonts/iosevka.sfl").unwrap();
+    let written = rewrite(&font);
+    let info = written.info_details.unwrap();
+    let common = written.common_details.unwrap();
+    assert_eq!(info.stretch_h, 100);
+    assert_eq!(info.unicode, 1);
+    assert_eq!(common.base, font.line_height);
+    assert_eq!(common.pages_count, 1);
+    assert!(font.chars.values().all(|c| c.x + c.width <= common.scale_w));
+    assert!(font
+        .chars
+        .values()
+        .all(|c| c.y + c.height <= common.scale_h));
+}
+
+#[test]
+fn sorted_output() {
+    let mut fnt = Vec::new();
+    kerned_font().write_fnt(&mut fnt).unwrap();
+    let fnt = String::from_utf8(fnt).unwrap();
+    let lines: Vec<&str> = fnt.lines().collect();
+    assert_eq!(lines[2], "page id=0 file=\"arial.png\"");
+    assert_eq!(lines[3], "chars count=3");
+    assert!(lines[4].starts_with("char id=65 "));
+    assert!(lines[6].starts_with("char id=86 "));
+    assert_eq!(lines[7], "kernings count=2");
+    assert_eq!(lines[8], "kerning first=65 second=86 amount=-2");
+}
+
+#[test]
+fn quotes_are_rejected() {
+    let mut named = kerned_font();
+    named.font_name = "Arial \"Bold\"".to_owned();
+    let mut paged = kerned_font();
+    paged.pages[0].image_path = "arial\".png".into();
+    let mut extra = kerned_font();
+    let c = extra.chars.get_mut(&65).unwrap();
+    c.extra.insert("glyph".to_owned(), "\"A".to_owned());
+    for font in [named, paged, extra].iter() {
+        match font.write_fnt(Vec::new()) {
+            Err(BMFontError::Unsupported(_)) => (),
+            other => panic!("Expected an Unsupported error, got {:?}", other),
+        }
+    }
+}
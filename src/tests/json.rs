@@ -0,0 +1,160 @@
+use super::from_loaded_setup_json;
+use crate::{BMFont, BMFontError, DiagnosticLevel, Format, ParseOptions};
+
+static MSDF_JSON: &str = r#"{
+  "pages": ["sdf_0.png", "sdf_1.png"],
+  "chars": [
+    {"id": 65, "index": 36, "char": "A", "width": 27, "height": 31, "xoffset": -2, "yoffset": 4, "xadvance": 23, "chnl": 15, "x": 0, "y": 0, "page": 0},
+    {"id": 86, "index": 57, "char": "V", "width": 27, "height": 31, "xoffset": -2, "yoffset": 4, "xadvance": 23, "chnl": 15, "x": 27, "y": 0, "page": 1}
+  ],
+  "info": {
+    "face": "Roboto é",
+    "size": 42,
+    "bold": 0,
+    "italic": 0,
+    "charset": ["A", "V"],
+    "unicode": 1,
+    "stretchH": 100,
+    "smooth": 1,
+    "aa": 1,
+    "padding": [2, 2, 2, 2],
+    "spacing": [0, 0]
+  },
+  "common": {"lineHeight": 49, "base": 39, "scaleW": 512, "scaleH": 256, "pages": 2, "packed": 0, "alphaChnl": 0, "redChnl": 0, "greenChnl": 0, "blueChnl": 0},
+  "distanceField": {"fieldType": "msdf", "distanceRange": 4},
+  "kernings": [{"first": 65, "second": 86, "amount": -3}]
+}"#;
+
+fn msdf_font() -> BMFont {
+    match BMFont::from_loaded(&Format::BMFontJson, MSDF_JSON, &["a.png", "b.png"]) {
+        Ok(bmfont) => bmfont,
+        Err(e) => panic!("Failed to load msdf font: {}", e),
+    }
+}
+
+#[test]
+fn info() {
+    let font = msdf_font();
+    let info = font.info_details.unwrap();
+    assert_eq!(font.font_name, "Roboto é");
+    assert_eq!(font.size, 42);
+    assert_eq!(info.charset, "");
+    assert_eq!(info.padding, [2, 2, 2, 2]);
+}
+
+#[test]
+fn common() {
+    let font = msdf_font();
+    let common = font.common_details.unwrap();
+    assert_eq!(font.line_height, 49);
+    assert_eq!(common.base, 39);
+    assert_eq!(common.scale_h, 256);
+}
+
+#[test]
+fn distance_field() {
+    let field = msdf_font().distance_field.unwrap();
+    assert_eq!(field.field_type, "msdf");
+    assert_eq!(field.distance_range, 4);
+    assert!(from_loaded_setup_json().distance_field.is_none());
+}
+
+#[test]
+fn pages_chars_and_kernings() {
+    let font = msdf_font();
+    assert_eq!(font.pages.len(), 2);
+    assert_eq!(font.pages[1].id, 1);
+    assert_eq!(font.chars[&86].page, 1);
+    assert_eq!(font.chars[&86].x, 27);
+    assert_eq!(font.kerning(65, 86), -3);
+}
+
+#[test]
+fn fractional_number() {
+    let broken = MSDF_JSON.replace("\"base\": 39", "\"base\": 39.5");
+    assert!(BMFont::from_loaded(&Format::BMFontJson, broken, &["a.png", "b.png"]).is_err());
+}
+
+#[test]
+fn trailing_comma() {
+    let broken = MSDF_JSON.replace("\"amount\": -3}]", "\"amount\": -3},]");
+    assert!(BMFont::from_loaded(&Format::BMFontJson, broken, &["a.png", "b.png"]).is_err());
+}
+
+#[test]
+fn deep_nesting() {
+    let nested = format!("{{\"chars\": {}", "[".repeat(100_000));
+    match BMFont::from_loaded(&Format::BMFontJson, nested, &["a.png"]) {
+        Err(BMFontError::Syntax(error)) => assert!(error.message.contains("nested")),
+        other => panic!("Expected a syntax error, got {:?}", other),
+    }
+}
+
+#[test]
+fn boolean_flags() {
+    let flagged = MSDF_JSON.replace("\"bold\": 0", "\"bold\": true");
+    let font = BMFont::from_loaded(&Format::BMFontJson, flagged, &["a.png", "b.png"]).unwrap();
+    assert_eq!(font.info_details.unwrap().bold, 1);
+}
+
+#[test]
+fn strict_rejects_unknown_keys() {
+    let extended = MSDF_JSON.replace("\"size\": 42,", "\"size\": 42, \"weight\": 400,");
+    match BMFont::from_loaded(&Format::BMFontJson, extended, &["a.png", "b.png"]) {
+        Err(BMFontError::Syntax(error)) => assert_eq!(error.message, "Unknown key info.weight"),
+        other => panic!("Expected a syntax error, got {:?}", other),
+    }
+}
+
+#[test]
+fn lenient_keeps_unknown_keys() {
+    let extended = MSDF_JSON
+        .replace("\"size\": 42,", "\"size\": 42, \"weight\": 400,")
+        .replace("\"index\": 36,", "\"index\": 36, \"tags\": [\"a\", 1],");
+    let mut diagnostics = Vec::new();
+    let mut options = ParseOptions::with_diagnostics(&mut diagnostics);
+    options.strict = false;
+    let font = BMFont::from_bytes_with(
+        &Format::BMFontJson,
+        extended.as_bytes(),
+        &["a.png", "b.png"],
+        &mut options,
+    )
+    .unwrap();
+    assert_eq!(font.info_details.unwrap().extra["weight"], "400");
+    assert_eq!(font.chars[&65].extra["tags"], "a,1");
+    // The index and char keys of msdf-bmfont-xml are known.
+    assert!(font.chars[&86].extra.is_empty());
+
+    let warnings: Vec<&str> = diagnostics
+        .iter()
+        .filter(|d| d.level == DiagnosticLevel::Warning)
+        .map(|d| d.message.as_str())
+        .collect();
+    assert_eq!(
+        warnings,
+        vec![
+            "Skipping unknown key info.weight",
+            "Skipping unknown key char.tags"
+        ]
+    );
+}
+
+#[test]
+fn round_trip() {
+    let font = msdf_font();
+    let mut json = Vec::new();
+    font.write_json(&mut json).unwrap();
+    let written = BMFont::from_bytes(&Format::BMFontJson, &json, &["a.png", "b.png"]).unwrap();
+    assert_eq!(written, font);
+}
+
+#[test]
+fn charset_name_round_trip() {
+    let mut font = msdf_font();
+    font.info_details.as_mut().unwrap().charset = "ANSI".to_owned();
+    let mut json = Vec::new();
+    font.write_json(&mut json).unwrap();
+    let written = BMFont::from_bytes(&Format::BMFontJson, &json, &["a.png", "b.png"]).unwrap();
+    assert_eq!(written.info_details.unwrap().charset, "ANSI");
+}
@@ -0,0 +1,42 @@
+use super::for_each_font;
+use crate::{BMFont, Format};
+
+pub static KERNED_BMFONT: &str = r#"info face="Arial" size=32 bold=0 italic=0 charset="" unicode=1 stretchH=100 smooth=1 aa=1 padding=0,0,0,0 spacing=1,1 outline=0
+common lineHeight=32 base=26 scaleW=256 scaleH=256 pages=1 packed=0 alphaChnl=1 redChnl=0 greenChnl=0 blueChnl=0
+page id=0 file="arial.png"
+chars count=3
+char id=65   x=0     y=0     width=20    height=22    xoffset=-1    yoffset=4     xadvance=19    page=0  chnl=15
+char id=84   x=20    y=0     width=18    height=22    xoffset=0     yoffset=4     xadvance=18    page=0  chnl=15
+char id=86   x=38    y=0     width=20    height=22    xoffset=-1    yoffset=4     xadvance=19    page=0  chnl=15
+kernings count=2
+kerning first=65  second=86  amount=-2
+kerning first=84  second=65  amount=-1
+"#;
+
+pub fn kerned_font() -> BMFont {
+    match BMFont::from_loaded(&Format::BMFont, KERNED_BMFONT, &["arial.png"]) {
+        Ok(bmfont) => bmfont,
+        Err(e) => panic!("Failed to load kerned font: {}", e),
+    }
+}
+
+#[test]
+fn kerning_amount() {
+    let font = kerned_font();
+    assert_eq!(font.kernings.len(), 2);
+    assert_eq!(font.kerning(65, 86), -2);
+    assert_eq!(font.kerning(84, 65), -1);
+}
+
+#[test]
+fn missing_kerning_pair() {
+    let font = kerned_font();
+    assert_eq!(font.kerning(86, 65), 0);
+}
+
+#[test]
+fn no_kernings() {
+    for_each_font(|font| {
+        assert!(font.kernings.is_empty());
+    });
+}
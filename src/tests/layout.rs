@@ -0,0 +1,424 @@
+use super::from_path_setup_sfl;
+use super::kerning::kerned_font;
+use crate::{Align, BMFont, Format, LayoutOptions, LineBreaks, Rect, TextLayout, VerticalAlign};
+
+#[test]
+fn glyph_rects() {
+    let font = kerned_font();
+    let glyphs = font.layout("AT", &LayoutOptions::default());
+    assert_eq!(glyphs.len(), 2);
+
+    let a = glyphs[0];
+    assert_eq!(
+        (a.id, a.index, a.line, a.page, a.channel),
+        (65, 0, 0, 0, 15)
+    );
+    // Hangs base=26 above the baseline, moved by xoffset=-1 and yoffset=4.
+    assert_eq!(
+        a.rect,
+        Rect {
+            x: -1.0,
+            y: -22.0,
+            width: 20.0,
+            height: 22.0
+        }
+    );
+    assert_eq!(
+        a.uv,
+        Rect {
+            x: 0.0,
+            y: 0.0,
+            width: 20.0 / 256.0,
+            height: 22.0 / 256.0
+        }
+    );
+
+    // A has xadvance=19, and there is no kerning between A and T.
+    assert_eq!(glyphs[1].rect.x, 19.0);
+    assert_eq!(glyphs[1].uv.x, 20.0 / 256.0);
+}
+
+#[test]
+fn kerning() {
+    let font = kerned_font();
+    let glyphs = font.layout("AV", &LayoutOptions::default());
+    assert_eq!(glyphs[1].rect.x, 19.0 - 2.0 - 1.0);
+
+    let options = LayoutOptions {
+        kerning: false,
+        ..LayoutOptions::default()
+    };
+    let glyphs = font.layout("AV", &options);
+    assert_eq!(glyphs[1].rect.x, 19.0 - 1.0);
+}
+
+#[test]
+fn newlines() {
+    let font = kerned_font();
+    let glyphs = font.layout("A\nVT\n\nT", &LayoutOptions::default());
+    let lines: Vec<(usize, usize, f32, f32)> = glyphs
+        .iter()
+        .map(|g| (g.index, g.line, g.rect.x, g.rect.y))
+        .collect();
+    assert_eq!(
+        lines,
+        vec![
+            (0, 0, -1.0, -22.0),
+            (2, 1, -1.0, 10.0),
+            (3, 1, 19.0, 10.0),
+            (6, 3, 0.0, 74.0)
+        ]
+    );
+}
+
+#[test]
+fn origin_scale_and_missing_chars() {
+    let font = kerned_font();
+    let options = LayoutOptions {
+        origin: [10.0, 100.0],
+        scale: 2.0,
+        ..LayoutOptions::default()
+    };
+    let glyphs = font.layout("éT", &options);
+    assert_eq!(glyphs.len(), 1);
+    assert_eq!(glyphs[0].index, 2);
+    assert_eq!(
+        glyphs[0].rect,
+        Rect {
+            x: 10.0,
+            y: 100.0 - 44.0,
+            width: 36.0,
+            height: 44.0
+        }
+    );
+}
+
+#[test]
+fn sfl_fonts_use_the_size_of_their_glyphs() {
+    let font = from_path_setup_sfl();
+    let width = font.chars.values().map(|c| c.x + c.width).max().unwrap() as f32;
+    let height = font.chars.values().map(|c| c.y + c.height).max().unwrap() as f32;
+    let glyphs = font.layout("AV", &LayoutOptions::default());
+    assert_eq!(glyphs.len(), 2);
+    for (glyph, id) in glyphs.iter().zip([65, 86].iter()) {
+        let c = &font.chars[id];
+        assert_eq!(glyph.uv.x, c.x as f32 / width);
+        assert_eq!(glyph.uv.y, c.y as f32 / height);
+        assert_eq!(glyph.uv.width, c.width as f32 / width);
+        assert_eq!(glyph.uv.height, c.height as f32 / height);
+        assert!(glyph.uv.x + glyph.uv.width <= 1.0 && glyph.uv.y + glyph.uv.height <= 1.0);
+        assert_eq!(glyph.rect.y, c.yoffset as f32 - font.line_height as f32);
+    }
+}
+
+#[test]
+fn fonts_without_a_scale_use_the_size_of_their_glyphs() {
+    let xml = r#"<font>
+  <info face="Arial" size="32"/>
+  <common lineHeight="32" base="26" pages="1"/>
+  <pages><page id="0" file="a.png"/></pages>
+  <chars count="2">
+    <char id="65" x="0" y="0" width="20" height="22" xadvance="19" page="0" chnl="15"/>
+    <char id="86" x="20" y="0" width="20" height="24" xadvance="19" page="0" chnl="15"/>
+  </chars>
+</font>"#;
+    let font = BMFont::from_loaded(&Format::BMFontXml, xml, &["a.png"]).unwrap();
+    let glyphs = font.layout("AV", &LayoutOptions::default());
+    assert_eq!(glyphs.len(), 2);
+    assert_eq!(
+        glyphs[1].uv,
+        Rect {
+            x: 0.5,
+            y: 0.0,
+            width: 0.5,
+            height: 1.0,
+        }
+    );
+
+    let mut empty = font.clone();
+    for c in empty.chars.values_mut() {
+        c.width = 0;
+        c.height = 0;
+        c.x = 0;
+    }
+    for glyph in empty.layout("AV", &LayoutOptions::default()) {
+        assert_eq!(glyph.uv, Rect::default());
+    }
+}
+
+/// The kerned font, with a space, lowercase letters and some CJK characters that all advance by
+/// 10 pixels.
+fn wrapping_font() -> BMFont {
+    let mut font = kerned_font();
+    let template = font.chars[&84].clone();
+    for ch in " abcdefghijklmnopqrstuvwxyz一二三四。".chars() {
+        let mut c = template.clone();
+        c.id = ch as u32;
+        c.xoffset = 0;
+        c.xadvance = 10;
+        if ch == ' ' {
+            c.width = 0;
+        }
+        font.chars.insert(c.id, c);
+    }
+    font
+}
+
+fn wrapped(font: &BMFont, text: &str, max_width: f32) -> TextLayout {
+    let options = LayoutOptions {
+        max_width: Some(max_width),
+        ..LayoutOptions::default()
+    };
+    font.layout_lines(text, &options)
+}
+
+fn line_texts<'a>(text: &'a str, layout: &TextLayout) -> Vec<&'a str> {
+    layout
+        .lines
+        .iter()
+        .map(|line| &text[line.range.clone()])
+        .collect()
+}
+
+#[test]
+fn wraps_at_whitespace() {
+    let font = wrapping_font();
+    let text = "aa bb  cc";
+    let layout = wrapped(&font, text, 50.0);
+    assert_eq!(line_texts(text, &layout), vec!["aa bb", "cc"]);
+    assert_eq!(layout.lines[0].width, 50.0);
+    assert_eq!(layout.lines[1].width, 20.0);
+
+    // The spaces the line was broken at are left out.
+    assert_eq!(layout.glyphs.len(), 7);
+    assert_eq!(layout.lines[1].glyphs, 5..7);
+    assert_eq!(layout.glyphs[5].index, 7);
+    assert_eq!(layout.glyphs[5].line, 1);
+    assert_eq!(layout.glyphs[5].rect.x, 0.0);
+    assert_eq!(layout.glyphs[5].rect.y, layout.glyphs[0].rect.y + 32.0);
+}
+
+#[test]
+fn breaks_long_words() {
+    let font = wrapping_font();
+    let text = "ab abcdefg";
+    let layout = wrapped(&font, text, 30.0);
+    assert_eq!(line_texts(text, &layout), vec!["ab", "abc", "def", "g"]);
+
+    // Every line gets a character, even if it does not fit.
+    let layout = wrapped(&font, text, 5.0);
+    assert_eq!(layout.lines.len(), 9);
+}
+
+#[test]
+fn wrapping_uses_kerning_and_scale() {
+    let font = kerned_font();
+    // A has xadvance=19 and V 19, with -2 of kerning between them.
+    assert_eq!(wrapped(&font, "AV", 36.0).lines.len(), 1);
+    assert_eq!(wrapped(&font, "AV", 35.0).lines.len(), 2);
+
+    let options = LayoutOptions {
+        max_width: Some(72.0),
+        scale: 2.0,
+        kerning: false,
+        ..LayoutOptions::default()
+    };
+    let layout = font.layout_lines("AV", &options);
+    assert_eq!(layout.lines.len(), 2);
+    assert_eq!(layout.lines[0].width, 38.0);
+}
+
+#[test]
+fn wrapped_and_hard_lines() {
+    let font = wrapping_font();
+    let text = "ab cd\n\nef";
+    let layout = wrapped(&font, text, 30.0);
+    assert_eq!(line_texts(text, &layout), vec!["ab", "cd", "", "ef"]);
+    assert_eq!(layout.lines[2].range, 6..6);
+    assert_eq!(layout.lines[2].glyphs, 4..4);
+    let lines: Vec<usize> = layout.glyphs.iter().map(|g| g.line).collect();
+    assert_eq!(lines, vec![0, 0, 1, 1, 3, 3]);
+
+    let layout = font.layout_lines(text, &LayoutOptions::default());
+    assert_eq!(line_texts(text, &layout), vec!["ab cd", "", "ef"]);
+    assert_eq!(layout.lines[0].width, 50.0);
+}
+
+#[test]
+fn cjk_without_unicode_line_breaks() {
+    let font = wrapping_font();
+    let text = "一二三。四";
+    let layout = wrapped(&font, text, 30.0);
+    assert_eq!(line_texts(text, &layout), vec!["一二三", "。四"]);
+}
+
+#[cfg(feature = "unicode-linebreak")]
+#[test]
+fn cjk_with_unicode_line_breaks() {
+    let font = wrapping_font();
+    let text = "一二三。四";
+    let options = LayoutOptions {
+        max_width: Some(30.0),
+        line_breaks: LineBreaks::Unicode,
+        ..LayoutOptions::default()
+    };
+    let layout = font.layout_lines(text, &options);
+    // No line may start with the full stop.
+    assert_eq!(line_texts(text, &layout), vec!["一二", "三。四"]);
+
+    let text = "ab cd-ef";
+    let layout = font.layout_lines(text, &options);
+    assert_eq!(line_texts(text, &layout), vec!["ab", "cd-", "ef"]);
+}
+
+#[cfg(not(feature = "unicode-linebreak"))]
+#[test]
+fn unicode_line_breaks_fall_back_to_whitespace() {
+    let font = wrapping_font();
+    let options = LayoutOptions {
+        max_width: Some(30.0),
+        line_breaks: LineBreaks::Unicode,
+        ..LayoutOptions::default()
+    };
+    for text in ["一二三。四", "ab cd-ef"].iter() {
+        let layout = font.layout_lines(text, &options);
+        assert_eq!(layout, wrapped(&font, text, 30.0));
+    }
+}
+
+fn aligned(font: &BMFont, text: &str, align: Align, max_width: Option<f32>) -> TextLayout {
+    let options = LayoutOptions {
+        max_width,
+        align,
+        ..LayoutOptions::default()
+    };
+    font.layout_lines(text, &options)
+}
+
+#[test]
+fn horizontal_alignment() {
+    let font = wrapping_font();
+    let text = "ab\nabcd";
+
+    let left = aligned(&font, text, Align::Left, None);
+    let center = aligned(&font, text, Align::Center, None);
+    let right = aligned(&font, text, Align::Right, Some(100.0));
+    let starts =
+        |layout: &TextLayout| -> Vec<f32> { layout.lines.iter().map(|line| line.x).collect() };
+    assert_eq!(starts(&left), vec![0.0, 0.0]);
+    // Without a max_width, the box is as wide as the widest line.
+    assert_eq!(starts(&center), vec![10.0, 0.0]);
+    assert_eq!(starts(&right), vec![80.0, 60.0]);
+
+    assert_eq!(center.glyphs[0].rect.x, left.glyphs[0].rect.x + 10.0);
+    assert_eq!(right.glyphs[5].rect.x, left.glyphs[5].rect.x + 60.0);
+    assert_eq!(right.glyphs[5].rect.y, left.glyphs[5].rect.y);
+
+    // The xoffset of the first glyph does not move the line.
+    let font = kerned_font();
+    let layout = aligned(&font, "A", Align::Center, Some(39.0));
+    assert_eq!(layout.lines[0].x, 10.0);
+    assert_eq!(layout.glyphs[0].rect.x, 10.0 - 1.0);
+}
+
+#[test]
+fn justify() {
+    let font = wrapping_font();
+    let text = "a b cd ef\nab c";
+    let layout = aligned(&font, text, Align::Justify, Some(70.0));
+    assert_eq!(line_texts(text, &layout), vec!["a b cd", "ef", "ab c"]);
+
+    // The 10 pixels left on the first line are spread over its two spaces.
+    assert_eq!(layout.lines[0].width, 70.0);
+    let xs: Vec<f32> = layout.glyphs[layout.lines[0].glyphs.clone()]
+        .iter()
+        .map(|g| g.rect.x)
+        .collect();
+    assert_eq!(xs, vec![0.0, 10.0, 25.0, 35.0, 50.0, 60.0]);
+
+    // The lines that end a paragraph are not justified.
+    assert_eq!(layout.lines[1].width, 20.0);
+    assert_eq!(layout.lines[2].width, 40.0);
+    assert_eq!(layout.glyphs[layout.lines[2].glyphs.end - 1].rect.x, 30.0);
+
+    // Neither are lines without spaces.
+    let layout = aligned(&font, "abcdefgh", Align::Justify, Some(70.0));
+    assert_eq!(layout.lines[0].width, 70.0);
+    assert_eq!(layout.glyphs[6].rect.x, 60.0);
+}
+
+#[test]
+fn vertical_alignment() {
+    // line_height=32 and base=26
+    let font = wrapping_font();
+    let baselines = |vertical_align: VerticalAlign| -> Vec<f32> {
+        let options = LayoutOptions {
+            origin: [0.0, 100.0],
+            vertical_align,
+            ..LayoutOptions::default()
+        };
+        let layout = font.layout_lines("a\nb", &options);
+        assert_eq!(
+            layout.glyphs[1].rect.y - layout.lines[1].baseline,
+            4.0 - 26.0
+        );
+        layout.lines.iter().map(|line| line.baseline).collect()
+    };
+    assert_eq!(baselines(VerticalAlign::Baseline), vec![100.0, 132.0]);
+    assert_eq!(baselines(VerticalAlign::Top), vec![126.0, 158.0]);
+    assert_eq!(baselines(VerticalAlign::Middle), vec![94.0, 126.0]);
+    assert_eq!(baselines(VerticalAlign::Bottom), vec![62.0, 94.0]);
+}
+
+#[test]
+fn measure() {
+    let font = kerned_font();
+    let metrics = font.measure("AV\nT");
+    assert_eq!(metrics.line_count, 2);
+    // A has xadvance=19, with -2 of kerning before V
+    assert_eq!(metrics.line_widths, vec![36.0, 18.0]);
+    assert_eq!(metrics.width, 36.0);
+    assert_eq!(metrics.height, 64.0);
+    assert_eq!((metrics.ascent, metrics.descent), (26.0, 6.0));
+    // From the xoffset=-1 of A to the end of V at 19 - 2 - 1 + 20, and from the top of the
+    // first line to the bottom of T on the second one.
+    assert_eq!(
+        metrics.ink,
+        Some(Rect {
+            x: -1.0,
+            y: -22.0,
+            width: 36.0 + 1.0,
+            height: 32.0 + 22.0
+        })
+    );
+
+    let metrics = font.measure("");
+    assert_eq!(metrics.line_count, 1);
+    assert_eq!(metrics.width, 0.0);
+    assert_eq!(metrics.ink, None);
+}
+
+#[test]
+fn measure_agrees_with_layout() {
+    let font = wrapping_font();
+    let text = "ab cd efg\nhijklm\n\nno";
+    let options = LayoutOptions {
+        max_width: Some(45.0),
+        scale: 1.5,
+        align: Align::Justify,
+        origin: [5.0, 7.0],
+        ..LayoutOptions::default()
+    };
+    let layout = font.layout_lines(text, &options);
+    let metrics = font.measure_with(text, &options);
+    assert_eq!(metrics.line_count, layout.lines.len());
+    let widths: Vec<f32> = layout.lines.iter().map(|line| line.width).collect();
+    assert_eq!(metrics.line_widths, widths);
+    assert!(metrics.width <= 45.0);
+
+    let ink = metrics.ink.unwrap();
+    for glyph in layout.glyphs.iter().filter(|g| g.rect.width > 0.0) {
+        assert!(glyph.rect.x >= ink.x && glyph.rect.x + glyph.rect.width <= ink.x + ink.width);
+        assert!(glyph.rect.y >= ink.y && glyph.rect.y + glyph.rect.height <= ink.y + ink.height);
+    }
+}
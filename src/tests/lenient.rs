@@ -0,0 +1,101 @@
+use crate::{BMFont, BMFontError, DiagnosticLevel, Format, ParseOptions};
+
+fn hiero_font() -> String {
+    let iosevka_fnt = include_str!("../../examples/fonts/iosevka.fnt");
+    iosevka_fnt
+        .replacen(" spacing=", " letterSpacing=-1 spacing=", 1)
+        .replacen("page id=0 ", "page id=0 layer=\"main layer\" ", 1)
+        .replacen("char id=33 ", "char id=33 xheight=4 ", 1)
+}
+
+fn load_lenient(contents: &str) -> BMFont {
+    let mut options = ParseOptions {
+        strict: false,
+        ..ParseOptions::default()
+    };
+    BMFont::from_bytes_with(
+        &Format::BMFont,
+        contents.as_bytes(),
+        &["a.png"],
+        &mut options,
+    )
+    .unwrap()
+}
+
+#[test]
+fn strict_rejects_unknown_keys() {
+    match BMFont::from_loaded(&Format::BMFont, hiero_font(), &["a.png"]) {
+        Err(BMFontError::Syntax(error)) => {
+            assert_eq!(error.message, "Unknown key info.letterSpacing");
+            assert_eq!(error.position.map(|p| p.line), Some(1));
+        }
+        other => panic!("Expected a Syntax error, got {:?}", other),
+    }
+}
+
+#[test]
+fn lenient_keeps_unknown_keys() {
+    let mut diagnostics = Vec::new();
+    let mut options = ParseOptions::with_diagnostics(&mut diagnostics);
+    options.strict = false;
+    let font = BMFont::from_bytes_with(
+        &Format::BMFont,
+        hiero_font().as_bytes(),
+        &["a.png"],
+        &mut options,
+    )
+    .unwrap();
+
+    let det = font.info_details.as_ref().unwrap();
+    assert_eq!(det.extra["letterSpacing"], "-1");
+    assert_eq!(font.pages[0].extra["layer"], "main layer");
+    assert_eq!(font.chars[&33].extra["xheight"], "4");
+    assert_eq!(font.chars[&33].x, 233);
+
+    let warnings: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| d.level == DiagnosticLevel::Warning)
+        .collect();
+    assert_eq!(warnings.len(), 3);
+    assert!(warnings[0].message.contains("info.letterSpacing"));
+
+    let plain = BMFont::from_path(&Format::BMFont, "examples/fonts/iosevka.fnt").unwrap();
+    assert_eq!(font.chars.len(), plain.chars.len());
+}
+
+#[test]
+fn extra_keys_are_written() {
+    let font = load_lenient(&hiero_font());
+
+    let mut fnt = Vec::new();
+    font.write_fnt(&mut fnt).unwrap();
+    let rewritten = load_lenient(std::str::from_utf8(&fnt).unwrap());
+    assert_eq!(rewritten, font);
+
+    let mut xml = Vec::new();
+    font.write_xml(&mut xml).unwrap();
+    let mut options = ParseOptions {
+        strict: false,
+        ..ParseOptions::default()
+    };
+    let rewritten =
+        BMFont::from_bytes_with(&Format::BMFontXml, &xml, &["a.png"], &mut options).unwrap();
+    assert_eq!(rewritten.info_details, font.info_details);
+    assert_eq!(rewritten.pages[0].extra, font.pages[0].extra);
+    assert_eq!(rewritten.chars[&33].extra, font.chars[&33].extra);
+
+    // Pages are only file names in JSON, so their unknown keys can not be written.
+    match font.write_json(Vec::new()) {
+        Err(BMFontError::Unsupported(_)) => (),
+        other => panic!("Expected an Unsupported error, got {:?}", other),
+    }
+    let mut font = font;
+    font.pages[0].extra.clear();
+    let mut json = Vec::new();
+    font.write_json(&mut json).unwrap();
+    let rewritten =
+        BMFont::from_bytes_with(&Format::BMFontJson, &json, &["a.png"], &mut options).unwrap();
+    assert_eq!(rewritten.info_details, font.info_details);
+    assert_eq!(rewritten.common_details, font.common_details);
+    assert_eq!(rewritten.chars, font.chars);
+}
@@ -0,0 +1,108 @@
+use super::kerning::KERNED_BMFONT;
+use crate::{BMCharacter, BMFont, FntDocument, Format, LayoutOptions, MissingGlyphPolicy};
+
+/// The kerned font with the invalid glyph BMFont exports, and a `?`.
+fn font_text() -> String {
+    KERNED_BMFONT.replace(
+        "chars count=3\n",
+        "chars count=5\n\
+         char id=-1   x=60    y=0     width=10    height=22    xoffset=1     yoffset=4     xadvance=12    page=0  chnl=15\n\
+         char id=63   x=70    y=0     width=12    height=22    xoffset=0     yoffset=4     xadvance=13    page=0  chnl=15\n",
+    )
+}
+
+fn font() -> BMFont {
+    BMFont::from_loaded(&Format::BMFont, font_text(), &["arial.png"]).unwrap()
+}
+
+#[test]
+fn invalid_glyph_is_parsed() {
+    let font = font();
+    assert_eq!(font.chars.len(), 5);
+    let invalid = &font.chars[&BMCharacter::INVALID_ID];
+    assert_eq!(invalid.id, BMCharacter::INVALID_ID);
+    assert_eq!(invalid.xadvance, 12);
+
+    let text = KERNED_BMFONT.replace("char id=65 ", "char id=-2 ");
+    assert!(BMFont::from_loaded(&Format::BMFont, text, &["arial.png"]).is_err());
+}
+
+#[test]
+fn invalid_glyph_is_written_as_minus_one() {
+    let font = font();
+    for format in [Format::BMFont, Format::BMFontXml, Format::BMFontJson] {
+        let mut written = Vec::new();
+        font.write(&format, &mut written).unwrap();
+        let text = String::from_utf8(written.clone()).unwrap();
+        assert!(
+            text.contains("id=-1 ") || text.contains("id=\"-1\"") || text.contains("\"id\": -1,")
+        );
+        let rewritten = BMFont::from_bytes(&format, &written, &["arial.png"]).unwrap();
+        assert_eq!(rewritten.chars, font.chars);
+    }
+
+    let mut written = Vec::new();
+    font.write_binary(&mut written).unwrap();
+    let rewritten = BMFont::from_bytes(&Format::BMFontBinary, &written, &["arial.png"]).unwrap();
+    assert_eq!(rewritten.chars, font.chars);
+
+    let text = font_text();
+    let mut document = FntDocument::parse(&text).unwrap();
+    document.update_from(&font);
+    assert_eq!(document.to_string(), text);
+}
+
+#[test]
+fn glyph_policies() {
+    let font = font();
+    let id = |policy| font.glyph('é', policy).map(|c| c.id);
+    assert_eq!(id(MissingGlyphPolicy::Skip), None);
+    assert_eq!(id(MissingGlyphPolicy::QuestionMark), Some('?' as u32));
+    assert_eq!(
+        id(MissingGlyphPolicy::InvalidGlyph),
+        Some(BMCharacter::INVALID_ID)
+    );
+    assert_eq!(id(MissingGlyphPolicy::Char(84)), Some(84));
+    // The font has no replacement character either.
+    assert_eq!(id(MissingGlyphPolicy::ReplacementChar), None);
+    assert_eq!(id(MissingGlyphPolicy::Char(66)), None);
+
+    assert_eq!(
+        font.glyph('A', MissingGlyphPolicy::QuestionMark)
+            .map(|c| c.id),
+        Some(65)
+    );
+}
+
+#[test]
+fn layout_with_policy() {
+    let font = font();
+    let text = "AéT\r\nπé";
+    let options = LayoutOptions {
+        missing_glyph: MissingGlyphPolicy::InvalidGlyph,
+        ..LayoutOptions::default()
+    };
+    let layout = font.layout_lines(text, &options);
+    let glyphs: Vec<(u32, usize, f32)> = layout
+        .glyphs
+        .iter()
+        .map(|g| (g.id, g.index, g.rect.x))
+        .collect();
+    // The control character \r is not replaced.
+    assert_eq!(
+        glyphs,
+        vec![
+            (65, 0, -1.0),
+            (BMCharacter::INVALID_ID, 1, 20.0),
+            (84, 3, 31.0),
+            (BMCharacter::INVALID_ID, 6, 1.0),
+            (BMCharacter::INVALID_ID, 8, 13.0),
+        ]
+    );
+    assert_eq!(layout.missing.iter().collect::<Vec<_>>(), vec![&'é', &'π']);
+    assert_eq!(layout.missing, font.missing_chars(text));
+
+    let skipped = font.layout_lines(text, &LayoutOptions::default());
+    assert_eq!(skipped.glyphs.len(), 2);
+    assert_eq!(skipped.missing, layout.missing);
+}
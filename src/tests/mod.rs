@@ -1,5 +1,25 @@
+mod binary;
+mod binary_writer;
 mod bmcharacter;
 mod bmfont;
+mod bmfont_ref;
+mod detect;
+mod diagnostics;
+mod error;
+mod fnt_document;
+mod fnt_writer;
+mod json;
+pub mod kerning;
+mod layout;
+mod lenient;
+mod missing_glyph;
+mod page_resolver;
+mod reader;
+#[cfg(feature = "serde")]
+mod serde;
+mod sfl_writer;
+mod validate;
+mod xml;
 
 use crate::{BMFont, Format};
 
@@ -9,6 +29,12 @@ pub fn for_each_font<F: Fn(&BMFont)>(f: F) {
         from_loaded_setup_sfl(),
         from_path_setup_bmfont(),
         from_loaded_setup_bmfont(),
+        from_path_setup_binary(),
+        from_bytes_setup_binary(),
+        from_path_setup_xml(),
+        from_loaded_setup_xml(),
+        from_path_setup_json(),
+        from_loaded_setup_json(),
     ];
     for font in fonts.iter() {
         f(font);
@@ -48,3 +74,60 @@ pub fn from_loaded_setup_bmfont() -> BMFont {
         Err(e) => panic!("Failed to load iosevka.fnt: {}", e),
     }
 }
+
+pub fn from_path_setup_binary() -> BMFont {
+    match BMFont::from_path(&Format::BMFontBinary, "examples/fonts/iosevka-binary.fnt") {
+        Ok(bmfont) => bmfont,
+        Err(e) => panic!("Failed to load iosevka-binary.fnt: {}", e),
+    }
+}
+
+pub fn from_bytes_setup_binary() -> BMFont {
+    let iosevka_binary = include_bytes!("../../examples/fonts/iosevka-binary.fnt");
+    match BMFont::from_bytes(
+        &Format::BMFontBinary,
+        iosevka_binary,
+        &["examples/fonts/iosevka.png"],
+    ) {
+        Ok(bmfont) => bmfont,
+        Err(e) => panic!("Failed to load iosevka-binary.fnt: {}", e),
+    }
+}
+
+pub fn from_path_setup_xml() -> BMFont {
+    match BMFont::from_path(&Format::BMFontXml, "examples/fonts/iosevka.xml") {
+        Ok(bmfont) => bmfont,
+        Err(e) => panic!("Failed to load iosevka.xml: {}", e),
+    }
+}
+
+pub fn from_loaded_setup_xml() -> BMFont {
+    let iosevka_xml = include_str!("../../examples/fonts/iosevka.xml");
+    match BMFont::from_loaded(
+        &Format::BMFontXml,
+        iosevka_xml,
+        &["examples/fonts/iosevka.png"],
+    ) {
+        Ok(bmfont) => bmfont,
+        Err(e) => panic!("Failed to load iosevka.xml: {}", e),
+    }
+}
+
+pub fn from_path_setup_json() -> BMFont {
+    match BMFont::from_path(&Format::BMFontJson, "examples/fonts/iosevka.json") {
+        Ok(bmfont) => bmfont,
+        Err(e) => panic!("Failed to load iosevka.json: {}", e),
+    }
+}
+
+pub fn from_loaded_setup_json() -> BMFont {
+    let iosevka_json = include_str!("../../examples/fonts/iosevka.json");
+    match BMFont::from_loaded(
+        &Format::BMFontJson,
+        iosevka_json,
+        &["examples/fonts/iosevka.png"],
+    ) {
+        Ok(bmfont) => bmfont,
+        Err(e) => panic!("Failed to load iosevka.json: {}", e),
+    }
+}
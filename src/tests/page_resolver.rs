@@ -0,0 +1,70 @@
+use super::{from_loaded_setup_bmfont, from_path_setup_bmfont};
+use crate::{resolve_with, BMFont, BMFontError, ExplicitList, Format, PageResolver};
+use crate::{ParseOptions, RelativeToFont, SearchDirectories};
+
+use std::path::{Path, PathBuf};
+
+const IOSEVKA_FNT: &[u8] = include_bytes!("../../examples/fonts/iosevka.fnt");
+
+fn load<P: PageResolver>(resolver: P) -> Result<BMFont, BMFontError> {
+    BMFont::from_reader_with_resolver(
+        &Format::BMFont,
+        IOSEVKA_FNT,
+        resolver,
+        &mut ParseOptions::default(),
+    )
+}
+
+#[test]
+fn relative_to_font() {
+    let mut resolver = RelativeToFont::new("examples/fonts/iosevka.fnt");
+    assert_eq!(
+        resolver.resolve(0, Path::new("iosevka.png")).unwrap(),
+        PathBuf::from("examples/fonts/iosevka.png")
+    );
+    let font = load(resolver).unwrap();
+    assert_eq!(font, from_path_setup_bmfont());
+}
+
+#[test]
+fn explicit_list() {
+    let resolver = ExplicitList::new(&["examples/fonts/iosevka.png"]);
+    let font = load(resolver).unwrap();
+    assert_eq!(font, from_loaded_setup_bmfont());
+
+    let empty: &[&str] = &[];
+    match load(ExplicitList::new(empty)) {
+        Err(BMFontError::Semantic(_)) => (),
+        other => panic!("Expected a Semantic error, got {:?}", other),
+    }
+}
+
+#[test]
+fn search_directories() {
+    // The fixtures have no images, so look up a font file instead.
+    let mut resolver = SearchDirectories::new(&["src", "examples/fonts"]);
+    assert_eq!(
+        resolver.resolve(0, Path::new("iosevka.fnt")).unwrap(),
+        PathBuf::from("examples/fonts/iosevka.fnt")
+    );
+    assert_eq!(
+        resolver.resolve(1, Path::new("lib.rs")).unwrap(),
+        PathBuf::from("src/lib.rs")
+    );
+
+    match load(resolver) {
+        Err(BMFontError::Io(_)) => (),
+        other => panic!("Expected an Io error, got {:?}", other),
+    }
+}
+
+#[test]
+fn resolve_handles() {
+    let font = from_path_setup_bmfont();
+    let handles = font
+        .resolve_pages(resolve_with(|id, file| {
+            (id, file.file_name().unwrap().to_owned())
+        }))
+        .unwrap();
+    assert_eq!(handles, vec![(0, "iosevka.png".into())]);
+}
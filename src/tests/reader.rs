@@ -0,0 +1,139 @@
+use super::kerning::{kerned_font, KERNED_BMFONT};
+use super::{from_path_setup_binary, from_path_setup_bmfont, from_path_setup_json};
+use super::{from_path_setup_sfl, from_path_setup_xml};
+use crate::{BMFont, BMFontError, Diagnostic, DiagnosticLevel, Format, ParseOptions, Position};
+
+use std::path::{Path, PathBuf};
+
+fn in_examples(_: u32, file: &Path) -> PathBuf {
+    Path::new("examples/fonts").join(file)
+}
+
+#[test]
+fn same_as_from_path() {
+    let fonts: [(&[u8], Format, BMFont); 5] = [
+        (
+            include_bytes!("../../examples/fonts/iosevka.sfl"),
+            Format::SFL,
+            from_path_setup_sfl(),
+        ),
+        (
+            include_bytes!("../../examples/fonts/iosevka.fnt"),
+            Format::BMFont,
+            from_path_setup_bmfont(),
+        ),
+        (
+            include_bytes!("../../examples/fonts/iosevka-binary.fnt"),
+            Format::BMFontBinary,
+            from_path_setup_binary(),
+        ),
+        (
+            include_bytes!("../../examples/fonts/iosevka.xml"),
+            Format::BMFontXml,
+            from_path_setup_xml(),
+        ),
+        (
+            include_bytes!("../../examples/fonts/iosevka.json"),
+            Format::BMFontJson,
+            from_path_setup_json(),
+        ),
+    ];
+    for (contents, format, expected) in fonts.iter() {
+        let font = BMFont::from_reader(format, *contents, in_examples).unwrap();
+        assert_eq!(&font, expected);
+    }
+}
+
+#[test]
+fn kernings_and_counts() {
+    let font = BMFont::from_reader(&Format::BMFont, KERNED_BMFONT.as_bytes(), |_, file| {
+        file.to_path_buf()
+    })
+    .unwrap();
+    assert_eq!(font, kerned_font());
+}
+
+#[test]
+fn windows_1252_face() {
+    let iosevka_fnt = include_str!("../../examples/fonts/iosevka.fnt");
+    let (before, after) = iosevka_fnt.split_once("Iosevka").unwrap();
+    let mut contents = b"\xEF\xBB\xBF".to_vec();
+    contents.extend(before.bytes());
+    contents.extend(b"Caf\xE9 \x99");
+    contents.extend(after.bytes());
+
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    let mut options = ParseOptions::with_diagnostics(&mut diagnostics);
+    let font = BMFont::from_reader_with(&Format::BMFont, &contents[..], in_examples, &mut options)
+        .unwrap();
+    assert_eq!(font.font_name, "Caf\u{E9} \u{2122}");
+    let warnings: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| d.level == DiagnosticLevel::Warning)
+        .collect();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].position, Some(Position { line: 1, column: 1 }));
+}
+
+#[test]
+fn windows_1252_fixture() {
+    let path = "examples/fonts/iosevka-1252.fnt";
+    let from_path = BMFont::from_path(&Format::BMFont, path).unwrap();
+    let file = std::fs::File::open(path).unwrap();
+    let from_reader = BMFont::from_reader(&Format::BMFont, file, in_examples).unwrap();
+    assert_eq!(from_path.font_name, "Iosevka Caf\u{E9}");
+    assert_eq!(from_path, from_reader);
+    assert_eq!(BMFont::from_path_auto(path).unwrap(), from_path);
+
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    let mut options = ParseOptions::with_diagnostics(&mut diagnostics);
+    BMFont::from_path_with(&Format::BMFont, path, &mut options).unwrap();
+    let warnings = diagnostics
+        .iter()
+        .filter(|d| d.level == DiagnosticLevel::Warning)
+        .count();
+    assert_eq!(warnings, 1);
+}
+
+#[test]
+fn errors_have_line_numbers() {
+    let iosevka_fnt = include_str!("../../examples/fonts/iosevka.fnt");
+    let broken = iosevka_fnt.replacen("char id=33 x=", "char id=33 x=abc", 1);
+    let line = broken
+        .lines()
+        .position(|line| line.starts_with("char id=33 "))
+        .unwrap()
+        + 1;
+    match BMFont::from_reader(&Format::BMFont, broken.as_bytes(), in_examples) {
+        Err(BMFontError::Syntax(error)) => {
+            assert_eq!(error.position, Some(Position { line, column: 14 }));
+        }
+        other => panic!("Expected a Syntax error, got {:?}", other),
+    }
+
+    let reordered = format!("{}char id=1\n", KERNED_BMFONT);
+    assert!(BMFont::from_reader(&Format::BMFont, reordered.as_bytes(), in_examples).is_err());
+}
+
+#[test]
+fn unexpected_lines_are_named() {
+    let reordered = format!("{}char id=1\n", KERNED_BMFONT);
+    let junk = KERNED_BMFONT.replacen("chars count=3", "glyphs count=3", 1);
+    let cases = [
+        (reordered, "Unexpected \"char\" after the kerning lines", 11),
+        (junk, "Unexpected \"glyphs\" after the page lines", 4),
+    ];
+    for (text, message, line) in cases.iter() {
+        let loaded = BMFont::from_loaded(&Format::BMFont, text.as_str(), &["arial.png"]);
+        let read = BMFont::from_reader(&Format::BMFont, text.as_bytes(), in_examples);
+        for result in [loaded, read].iter() {
+            match result {
+                Err(BMFontError::Syntax(error)) => {
+                    assert_eq!(&error.message, message);
+                    assert_eq!(error.position.map(|p| p.line), Some(*line));
+                }
+                other => panic!("Expected a Syntax error, got {:?}", other),
+            }
+        }
+    }
+}
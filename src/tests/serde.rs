@@ -0,0 +1,99 @@
+use super::for_each_font;
+use super::kerning::kerned_font;
+use crate::{BMFont, BMFontError, BMFontRef, Diagnostic, DiagnosticLevel, Format};
+use crate::{IssueKind, Position, Severity, SyntaxError, ValidationIssue};
+
+#[test]
+fn round_trip() {
+    for_each_font(|font| {
+        let json = serde_json::to_string(font).unwrap();
+        let read: BMFont = serde_json::from_str(&json).unwrap();
+        assert_eq!(&read, font);
+    });
+}
+
+#[test]
+fn field_names() {
+    let value = serde_json::to_value(kerned_font()).unwrap();
+    assert_eq!(value["font_name"], "Arial");
+    assert_eq!(value["line_height"], 32);
+    assert_eq!(value["info_details"]["stretch_h"], 100);
+    assert_eq!(value["common_details"]["pages_count"], 1);
+    assert_eq!(value["pages"][0]["image_path"], "arial.png");
+    assert_eq!(value["chars"][0]["id"], 65);
+    assert_eq!(value["chars"][0]["channel"], 15);
+    assert_eq!(
+        value["kernings"],
+        serde_json::json!([
+            { "first": 65, "second": 86, "amount": -2 },
+            { "first": 84, "second": 65, "amount": -1 },
+        ])
+    );
+}
+
+#[test]
+fn output_is_stable() {
+    let font = kerned_font();
+    let json = serde_json::to_string(&font).unwrap();
+    let copy = font.clone();
+    assert_eq!(serde_json::to_string(&copy).unwrap(), json);
+}
+
+#[test]
+fn borrowed_round_trip() {
+    let iosevka_fnt = include_str!("../../examples/fonts/iosevka.fnt");
+    let font = BMFontRef::parse(&Format::BMFont, iosevka_fnt).unwrap();
+    let json = serde_json::to_string(&font).unwrap();
+    let read: BMFontRef = serde_json::from_str(&json).unwrap();
+    assert_eq!(read, font);
+}
+
+#[test]
+fn missing_extra_is_empty() {
+    let json = r#"{ "id": 0, "image_path": "a.png" }"#;
+    let page: crate::Page = serde_json::from_str(json).unwrap();
+    assert!(page.extra.is_empty());
+}
+
+#[test]
+fn variant_names() {
+    let value = serde_json::to_value(Format::BMFontXml).unwrap();
+    assert_eq!(value, "BMFontXml");
+    let issue = ValidationIssue {
+        severity: Severity::Warning,
+        kind: IssueKind::DuplicateCharId(65),
+        message: "Duplicate".to_owned(),
+    };
+    assert_eq!(
+        serde_json::to_value(&issue).unwrap(),
+        serde_json::json!({
+            "severity": "Warning",
+            "kind": { "DuplicateCharId": 65 },
+            "message": "Duplicate",
+        })
+    );
+}
+
+#[test]
+fn diagnostics_and_errors() {
+    let diagnostic = Diagnostic {
+        level: DiagnosticLevel::Warning,
+        message: "Skipping unknown key info.letterSpacing".to_owned(),
+        position: Some(Position { line: 1, column: 5 }),
+    };
+    let json = serde_json::to_string(&diagnostic).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Diagnostic>(&json).unwrap(),
+        diagnostic
+    );
+
+    let error = match BMFont::from_loaded(&Format::BMFont, "info face=", &["a.png"]) {
+        Err(BMFontError::Syntax(error)) => error,
+        other => panic!("Expected a Syntax error, got {:?}", other),
+    };
+    let value = serde_json::to_value(&error).unwrap();
+    assert_eq!(value["message"], error.message.as_str());
+    assert_eq!(value["position"]["line"], 1);
+    let read: SyntaxError = serde_json::from_value(value).unwrap();
+    assert_eq!(read, error);
+}
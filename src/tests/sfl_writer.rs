@@ -0,0 +1,81 @@
+use super::kerning::kerned_font;
+use super::{for_each_font, from_path_setup_sfl};
+use crate::{BMFont, Format, Page};
+
+use std::collections::BTreeMap;
+
+fn rewrite(font: &BMFont) -> BMFont {
+    let mut sfl = Vec::new();
+    font.write_sfl(&mut sfl).unwrap();
+    let image_path = font.pages[0].image_path.to_string_lossy().into_owned();
+    match BMFont::from_bytes(&Format::SFL, &sfl, &[&image_path]) {
+        Ok(bmfont) => bmfont,
+        Err(e) => panic!("Failed to load written font: {}", e),
+    }
+}
+
+#[test]
+fn round_trip() {
+    for_each_font(|font| {
+        let written = rewrite(font);
+        assert_eq!(written.font_name, font.font_name);
+        assert_eq!(written.size, font.size);
+        assert_eq!(written.line_height, font.line_height);
+        assert_eq!(written.pages, font.pages);
+        assert_eq!(written.chars, font.chars);
+    });
+}
+
+#[test]
+fn same_layout() {
+    let font = from_path_setup_sfl();
+    let mut sfl = Vec::new();
+    font.write_sfl(&mut sfl).unwrap();
+    let sfl = String::from_utf8(sfl).unwrap();
+    let original = include_str!("../../examples/fonts/iosevka.sfl");
+
+    let mut written_lines: Vec<&str> = sfl.lines().collect();
+    let mut original_lines: Vec<&str> = original.lines().collect();
+    assert_eq!(
+        written_lines[..4],
+        ["Iosevka", "32 53", "examples/fonts/iosevka.png", "486"]
+    );
+    written_lines.sort();
+    original_lines.sort();
+    assert_eq!(written_lines.len(), original_lines.len());
+    for (written, original) in written_lines.iter().zip(original_lines.iter()) {
+        if !original.ends_with(".png") {
+            assert_eq!(written, original);
+        }
+    }
+}
+
+#[test]
+fn quoted_name() {
+    let mut font = from_path_setup_sfl();
+    font.font_name = "Iosevka Term".to_owned();
+    assert_eq!(rewrite(&font).font_name, "Iosevka Term");
+}
+
+#[test]
+fn multiple_pages() {
+    let mut font = from_path_setup_sfl();
+    font.pages.push(Page {
+        id: 1,
+        image_path: "iosevka_1.png".into(),
+        extra: BTreeMap::new(),
+    });
+    assert!(font.write_sfl(Vec::new()).is_err());
+}
+
+#[test]
+fn non_default_channel() {
+    let mut font = from_path_setup_sfl();
+    font.chars.get_mut(&65).unwrap().channel = 4;
+    assert!(font.write_sfl(Vec::new()).is_err());
+}
+
+#[test]
+fn kernings() {
+    assert!(kerned_font().write_sfl(Vec::new()).is_err());
+}
@@ -0,0 +1,167 @@
+use super::for_each_font;
+use super::kerning::{kerned_font, KERNED_BMFONT};
+use crate::{BMFont, Format, IssueKind, Page, ParseOptions, Severity};
+
+const BROKEN: &str = "info face=Broken size=32
+common lineHeight=32 base=26 scaleW=64 scaleH=64 pages=3
+page id=0 file=\"a.png\"
+page id=0 file=\"b.png\"
+chars count=5
+char id=65 x=0 y=0 width=20 height=20 xoffset=0 yoffset=0 xadvance=20 page=0 chnl=15
+char id=66 x=10 y=10 width=20 height=20 xoffset=0 yoffset=0 xadvance=20 page=0 chnl=15
+char id=67 x=50 y=50 width=20 height=10 xoffset=0 yoffset=0 xadvance=20 page=0 chnl=15
+char id=68 x=0 y=0 width=20 height=20 xoffset=0 yoffset=0 xadvance=20 page=2 chnl=15
+kernings count=0
+kerning first=65 second=66 amount=-1";
+
+fn broken_font() -> BMFont {
+    let mut options = ParseOptions {
+        strict: false,
+        ..ParseOptions::default()
+    };
+    BMFont::from_bytes_with(
+        &Format::BMFont,
+        BROKEN.as_bytes(),
+        &["a.png", "b.png"],
+        &mut options,
+    )
+    .unwrap()
+}
+
+#[test]
+fn example_fonts_are_valid() {
+    for_each_font(|font| assert_eq!(font.validate(), vec![]));
+    assert_eq!(kerned_font().validate(), vec![]);
+}
+
+#[test]
+fn all_issues() {
+    let kinds: Vec<(Severity, IssueKind)> = broken_font()
+        .validate()
+        .into_iter()
+        .map(|issue| (issue.severity, issue.kind))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (Severity::Error, IssueKind::GlyphOutOfBounds(67)),
+            (Severity::Error, IssueKind::MissingPage(68)),
+            (Severity::Error, IssueKind::DuplicatePageId(0)),
+            (Severity::Warning, IssueKind::OverlappingGlyphs(65, 66)),
+            (
+                Severity::Warning,
+                IssueKind::PagesCount {
+                    declared: 3,
+                    found: 2
+                }
+            ),
+            (
+                Severity::Warning,
+                IssueKind::CharsCount {
+                    declared: 5,
+                    found: 4
+                }
+            ),
+            (
+                Severity::Warning,
+                IssueKind::KerningsCount {
+                    declared: 0,
+                    found: 1
+                }
+            ),
+        ]
+    );
+}
+
+#[test]
+fn overlaps_need_a_shared_page_and_channel() {
+    let mut font = kerned_font();
+    font.chars.get_mut(&84).unwrap().x = 10;
+    let issues = font.validate();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].kind, IssueKind::OverlappingGlyphs(65, 84));
+    assert_eq!(issues[0].to_string(), "Warning: chars 65 and 84 overlap");
+
+    font.chars.get_mut(&65).unwrap().channel = 1;
+    font.chars.get_mut(&84).unwrap().channel = 2;
+    assert_eq!(font.validate(), vec![]);
+
+    font.chars.get_mut(&84).unwrap().channel = 15;
+    font.chars.get_mut(&84).unwrap().page = 1;
+    font.pages.push(Page {
+        id: 1,
+        image_path: "arial_1.png".into(),
+        extra: Default::default(),
+    });
+    font.common_details.as_mut().unwrap().pages_count = 2;
+    assert_eq!(font.validate(), vec![]);
+}
+
+#[test]
+fn shared_and_empty_rectangles_do_not_overlap() {
+    let mut font = kerned_font();
+    let mut alias = font.chars[&65].clone();
+    alias.id = 192;
+    font.chars.insert(192, alias);
+    let mut space = font.chars[&65].clone();
+    space.id = 32;
+    space.width = 0;
+    font.chars.insert(32, space);
+    font.declared_counts.chars = None;
+    assert_eq!(font.validate(), vec![]);
+}
+
+#[test]
+fn char_stored_under_another_id() {
+    let mut font = kerned_font();
+    let c = font.chars.remove(&86).unwrap();
+    font.chars.insert(87, c);
+    let issues = font.validate();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].severity, Severity::Error);
+    assert_eq!(issues[0].kind, IssueKind::DuplicateCharId(86));
+}
+
+#[test]
+fn duplicate_char_lines() {
+    let text = KERNED_BMFONT.replace(
+        "chars count=3\n",
+        "chars count=4\n\
+         char id=65   x=60    y=0     width=20    height=22    xoffset=-1    yoffset=4     xadvance=19    page=0  chnl=15\n",
+    );
+    let loaded = BMFont::from_loaded(&Format::BMFont, text.as_str(), &["arial.png"]).unwrap();
+    let read = BMFont::from_reader(&Format::BMFont, text.as_bytes(), |_, file| {
+        file.to_path_buf()
+    })
+    .unwrap();
+    for font in [loaded, read].iter() {
+        assert_eq!(font.declared_counts.duplicate_chars, vec![65]);
+        let kinds: Vec<(Severity, IssueKind)> = font
+            .validate()
+            .into_iter()
+            .map(|issue| (issue.severity, issue.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (Severity::Error, IssueKind::DuplicateCharId(65)),
+                (
+                    Severity::Warning,
+                    IssueKind::CharsCount {
+                        declared: 4,
+                        found: 3
+                    }
+                ),
+            ]
+        );
+    }
+}
+
+#[test]
+fn declared_counts_are_not_compared() {
+    let font = kerned_font();
+    let mut miscounted = font.clone();
+    miscounted.declared_counts.chars = Some(1);
+    assert_eq!(miscounted, font);
+    assert_ne!(miscounted.validate(), font.validate());
+}
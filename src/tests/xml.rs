@@ -0,0 +1,134 @@
+use crate::{BMFont, BMFontError, DiagnosticLevel, Format, ParseOptions};
+
+static KERNED_XML: &str = r#"<?xml version="1.0"?>
+<!-- Exported by a test -->
+<font>
+  <info face="Arial &amp; Friends" size="32" bold="1" italic="0" charset="" unicode="1" stretchH="100" smooth="1" aa="1" padding="1,2,3,4" spacing="1,1" outline="0"/>
+  <common lineHeight="32" base="26" scaleW="256" scaleH="256" pages="2" packed="0" alphaChnl="1" redChnl="0" greenChnl="0" blueChnl="0"/>
+  <pages>
+    <page id="0" file="arial_0.png" />
+    <page id="1" file='arial_1.png' />
+  </pages>
+  <chars count="2">
+    <char id="65" x="0" y="0" width="20" height="22" xoffset="-1" yoffset="4" xadvance="19" page="0" chnl="15" />
+    <char id="86" x="0" y="0" width="20" height="22" xoffset="-1" yoffset="4" xadvance="19" page="1" chnl="15" />
+  </chars>
+  <kernings count="1">
+    <kerning first="65" second="86" amount="-2" />
+  </kernings>
+</font>
+"#;
+
+fn kerned_font() -> BMFont {
+    match BMFont::from_loaded(&Format::BMFontXml, KERNED_XML, &["a.png", "b.png"]) {
+        Ok(bmfont) => bmfont,
+        Err(e) => panic!("Failed to load kerned font: {}", e),
+    }
+}
+
+#[test]
+fn info() {
+    let font = kerned_font();
+    let info = font.info_details.unwrap();
+    assert_eq!(font.font_name, "Arial & Friends");
+    assert_eq!(info.bold, 1);
+    assert_eq!(info.stretch_h, 100);
+    assert_eq!(info.padding, [1, 2, 3, 4]);
+    assert_eq!(info.spacing, [1, 1]);
+}
+
+#[test]
+fn common() {
+    let font = kerned_font();
+    let common = font.common_details.unwrap();
+    assert_eq!(font.line_height, 32);
+    assert_eq!(common.base, 26);
+    assert_eq!(common.pages_count, 2);
+    assert_eq!(common.alpha_channel, 1);
+}
+
+#[test]
+fn pages_and_chars() {
+    let font = kerned_font();
+    assert_eq!(font.pages.len(), 2);
+    assert_eq!(font.pages[1].id, 1);
+    assert_eq!(font.chars[&86].page, 1);
+    assert_eq!(font.chars[&65].xoffset, -1);
+}
+
+#[test]
+fn kernings() {
+    let font = kerned_font();
+    assert_eq!(font.kerning(65, 86), -2);
+}
+
+#[test]
+fn unclosed_element() {
+    let broken = KERNED_XML.replace("</chars>", "");
+    assert!(BMFont::from_loaded(&Format::BMFontXml, broken, &["a.png", "b.png"]).is_err());
+}
+
+#[test]
+fn bad_number() {
+    let broken = KERNED_XML.replace("base=\"26\"", "base=\"twenty\"");
+    assert!(BMFont::from_loaded(&Format::BMFontXml, broken, &["a.png", "b.png"]).is_err());
+}
+
+#[test]
+fn deep_nesting() {
+    let nested = format!("<font>{}", "<a>".repeat(100_000));
+    match BMFont::from_loaded(&Format::BMFontXml, nested, &["a.png"]) {
+        Err(BMFontError::Syntax(error)) => assert!(error.message.contains("nested")),
+        other => panic!("Expected a syntax error, got {:?}", other),
+    }
+}
+
+#[test]
+fn strict_rejects_unknown_attributes() {
+    let extended = KERNED_XML.replace("<page id=\"0\" ", "<page id=\"0\" layer=\"main\" ");
+    match BMFont::from_loaded(&Format::BMFontXml, extended, &["a.png", "b.png"]) {
+        Err(BMFontError::Syntax(error)) => assert_eq!(error.message, "Unknown key page.layer"),
+        other => panic!("Expected a syntax error, got {:?}", other),
+    }
+}
+
+#[test]
+fn lenient_keeps_unknown_attributes() {
+    let extended = KERNED_XML
+        .replace("<page id=\"0\" ", "<page id=\"0\" layer=\"main\" ")
+        .replace("amount=\"-2\"", "amount=\"-2\" source=\"gpos\"");
+    let mut diagnostics = Vec::new();
+    let mut options = ParseOptions::with_diagnostics(&mut diagnostics);
+    options.strict = false;
+    let font = BMFont::from_bytes_with(
+        &Format::BMFontXml,
+        extended.as_bytes(),
+        &["a.png", "b.png"],
+        &mut options,
+    )
+    .unwrap();
+    assert_eq!(font.pages[0].extra["layer"], "main");
+    assert_eq!(font.kerning(65, 86), -2);
+
+    let warnings: Vec<&str> = diagnostics
+        .iter()
+        .filter(|d| d.level == DiagnosticLevel::Warning)
+        .map(|d| d.message.as_str())
+        .collect();
+    assert_eq!(
+        warnings,
+        vec![
+            "Skipping unknown key page.layer",
+            "Skipping unknown key kerning.source"
+        ]
+    );
+}
+
+#[test]
+fn round_trip() {
+    let font = kerned_font();
+    let mut xml = Vec::new();
+    font.write_xml(&mut xml).unwrap();
+    let written = BMFont::from_bytes(&Format::BMFontXml, &xml, &["a.png", "b.png"]).unwrap();
+    assert_eq!(written, font);
+}
@@ -0,0 +1,267 @@
+use crate::{BMCharacter, BMFont};
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+
+/// How serious a [`ValidationIssue`][issue] is.
+///
+/// [issue]: struct.ValidationIssue.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
+pub enum Severity {
+    /// The font is suspicious, but can still be rendered, e.g. a declared count is off.
+    Warning,
+    /// The font can not be rendered correctly, e.g. a glyph is outside of its page.
+    Error,
+}
+
+/// What a [`ValidationIssue`][issue] is about.
+///
+/// [issue]: struct.ValidationIssue.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
+pub enum IssueKind {
+    /// The rectangle of the char with this id does not fit in `scale_w` x `scale_h`.
+    GlyphOutOfBounds(u32),
+    /// The char with this id is on a page the font does not have.
+    MissingPage(u32),
+    /// More than one page has this id.
+    DuplicatePageId(u32),
+    /// More than one char in the font file has this id, and only the last one was kept. Also
+    /// reported when a char with this id is stored under another id in `chars`.
+    DuplicateCharId(u32),
+    /// The rectangles of the chars with these ids overlap on the same page and channel.
+    OverlappingGlyphs(u32, u32),
+    /// `pages_count` does not match the number of pages.
+    PagesCount {
+        /// The count in the common block
+        declared: usize,
+        /// The number of pages
+        found: usize,
+    },
+    /// The declared chars count does not match the number of chars.
+    CharsCount {
+        /// The count in the font file
+        declared: usize,
+        /// The number of chars
+        found: usize,
+    },
+    /// The declared kernings count does not match the number of kerning pairs.
+    KerningsCount {
+        /// The count in the font file
+        declared: usize,
+        /// The number of kerning pairs
+        found: usize,
+    },
+}
+
+/// A problem found by [`BMFont::validate`][validate].
+///
+/// [validate]: struct.BMFont.html#method.validate
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub struct ValidationIssue {
+    /// How serious the issue is.
+    pub severity: Severity,
+    /// What the issue is about.
+    pub kind: IssueKind,
+    /// Description of the issue.
+    pub message: String,
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{:?}: {}", self.severity, self.message)
+    }
+}
+
+impl BMFont {
+    /// Checks that the font is consistent: glyphs fit in their pages, refer to existing pages
+    /// and do not overlap, ids are unique and declared counts match the contents. Returns the
+    /// issues found, the checks coming in that order and each sorted by char or page id.
+    ///
+    /// Glyph bounds and `pages_count` are only checked when the font has
+    /// [`CommonDetails`][common_details]. Glyphs that are empty, share the exact same rectangle
+    /// or are on different channels are not considered overlapping.
+    ///
+    /// # Examples
+    /// ```
+    /// use bmfont_parser::{BMFont, Format, IssueKind};
+    ///
+    /// let mut bmfont = BMFont::from_path(&Format::BMFont, "examples/fonts/iosevka.fnt").unwrap();
+    /// assert!(bmfont.validate().is_empty());
+    ///
+    /// bmfont.chars.get_mut(&65).unwrap().page = 1;
+    /// let issues = bmfont.validate();
+    /// assert_eq!(issues[0].kind, IssueKind::MissingPage(65));
+    /// ```
+    ///
+    /// [common_details]: struct.CommonDetails.html
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let chars: BTreeMap<u32, &BMCharacter> = self.chars.iter().map(|(&k, c)| (k, c)).collect();
+
+        if let Some(common) = &self.common_details {
+            for c in chars.values() {
+                let right = u64::from(c.x) + u64::from(c.width);
+                let bottom = u64::from(c.y) + u64::from(c.height);
+                if right > u64::from(common.scale_w) || bottom > u64::from(common.scale_h) {
+                    issues.push(error(
+                        IssueKind::GlyphOutOfBounds(c.id),
+                        format!(
+                            "char {} ends at ({}, {}), outside of the {}x{} pages",
+                            c.id, right, bottom, common.scale_w, common.scale_h
+                        ),
+                    ));
+                }
+            }
+        }
+
+        let page_ids: BTreeSet<u32> = self.pages.iter().map(|page| page.id).collect();
+        for c in chars.values().filter(|c| !page_ids.contains(&c.page)) {
+            issues.push(error(
+                IssueKind::MissingPage(c.id),
+                format!("char {} is on page {}, which does not exist", c.id, c.page),
+            ));
+        }
+
+        let mut page_counts = BTreeMap::new();
+        for page in self.pages.iter() {
+            *page_counts.entry(page.id).or_insert(0) += 1;
+        }
+        for (&id, &count) in page_counts.iter().filter(|(_, &count)| count > 1) {
+            issues.push(error(
+                IssueKind::DuplicatePageId(id),
+                format!("{} pages have id {}", count, id),
+            ));
+        }
+
+        let mut duplicates = self.declared_counts.duplicate_chars.clone();
+        duplicates.sort_unstable();
+        for id in duplicates {
+            issues.push(error(
+                IssueKind::DuplicateCharId(id),
+                format!("more than one char has id {}", id),
+            ));
+        }
+        for (&key, c) in chars.iter().filter(|(&key, c)| key != c.id) {
+            issues.push(error(
+                IssueKind::DuplicateCharId(c.id),
+                format!("char {} is stored under id {}", c.id, key),
+            ));
+        }
+
+        for (first, second) in overlapping(&chars) {
+            issues.push(warning(
+                IssueKind::OverlappingGlyphs(first, second),
+                format!("chars {} and {} overlap", first, second),
+            ));
+        }
+
+        if let Some(common) = &self.common_details {
+            let declared = common.pages_count as usize;
+            if declared != self.pages.len() {
+                issues.push(warning(
+                    IssueKind::PagesCount {
+                        declared,
+                        found: self.pages.len(),
+                    },
+                    format!(
+                        "pages count is {}, but {} pages were found",
+                        declared,
+                        self.pages.len()
+                    ),
+                ));
+            }
+        }
+        if let Some(declared) = self.declared_counts.chars {
+            if declared != self.chars.len() {
+                issues.push(warning(
+                    IssueKind::CharsCount {
+                        declared,
+                        found: self.chars.len(),
+                    },
+                    format!(
+                        "chars count is {}, but {} chars were found",
+                        declared,
+                        self.chars.len()
+                    ),
+                ));
+            }
+        }
+        if let Some(declared) = self.declared_counts.kernings {
+            if declared != self.kernings.len() {
+                issues.push(warning(
+                    IssueKind::KerningsCount {
+                        declared,
+                        found: self.kernings.len(),
+                    },
+                    format!(
+                        "kernings count is {}, but {} kernings were found",
+                        declared,
+                        self.kernings.len()
+                    ),
+                ));
+            }
+        }
+
+        issues
+    }
+}
+
+fn error(kind: IssueKind, message: String) -> ValidationIssue {
+    ValidationIssue {
+        severity: Severity::Error,
+        kind,
+        message,
+    }
+}
+
+fn warning(kind: IssueKind, message: String) -> ValidationIssue {
+    ValidationIssue {
+        severity: Severity::Warning,
+        kind,
+        message,
+    }
+}
+
+/// Returns the id pairs of the glyphs whose rectangles overlap, sorted. Each page is swept from
+/// left to right, so only glyphs that start before the current one ends are compared.
+fn overlapping(chars: &BTreeMap<u32, &BMCharacter>) -> Vec<(u32, u32)> {
+    let mut pages: BTreeMap<u32, Vec<&BMCharacter>> = BTreeMap::new();
+    for c in chars.values().filter(|c| c.width > 0 && c.height > 0) {
+        pages.entry(c.page).or_default().push(c);
+    }
+
+    let mut pairs = Vec::new();
+    for glyphs in pages.values_mut() {
+        glyphs.sort_by_key(|c| (c.x, c.id));
+        for (idx, a) in glyphs.iter().enumerate() {
+            let right = u64::from(a.x) + u64::from(a.width);
+            for b in glyphs[idx + 1..]
+                .iter()
+                .take_while(|b| u64::from(b.x) < right)
+            {
+                let same_rect = (a.x, a.y, a.width, a.height) == (b.x, b.y, b.width, b.height);
+                let overlaps_y = u64::from(b.y) < u64::from(a.y) + u64::from(a.height)
+                    && u64::from(a.y) < u64::from(b.y) + u64::from(b.height);
+                if overlaps_y && !same_rect && channels(a) & channels(b) != 0 {
+                    pairs.push((a.id.min(b.id), a.id.max(b.id)));
+                }
+            }
+        }
+    }
+    pairs.sort_unstable();
+    pairs
+}
+
+/// The channels a glyph is in, where 0 means all of them.
+fn channels(c: &BMCharacter) -> u32 {
+    match c.channel {
+        0 => 15,
+        channel => channel,
+    }
+}
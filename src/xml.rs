@@ -0,0 +1,257 @@
+//! A minimal XML reader, supporting just enough of XML to read the XML .fnt format: elements,
+//! attributes, the XML declaration, comments, doctypes and the predefined and numeric entities.
+//! Text content is skipped.
+
+use crate::{syntax_err, BMFontError};
+
+/// How deep elements may be nested, so that a malicious file can not overflow the stack.
+const MAX_DEPTH: usize = 128;
+
+/// A single XML element with its attributes and child elements.
+#[derive(Debug, Clone)]
+pub(crate) struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Element>,
+}
+
+impl Element {
+    /// Returns the value of the attribute with the given name.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the first child element with the given name.
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    /// Returns all child elements with the given name.
+    pub fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+}
+
+/// Parses the given XML document, returning its root element.
+pub(crate) fn parse(text: &str) -> Result<Element, BMFontError> {
+    let mut reader = Reader {
+        text,
+        cursor: 0,
+        depth: 0,
+    };
+    reader.skip("\u{feff}");
+    reader.skip_misc()?;
+    let root = reader.element()?;
+    reader.skip_misc()?;
+    if reader.cursor != text.len() {
+        syntax_err("Something found after the XML root element, should not")
+    } else {
+        Ok(root)
+    }
+}
+
+struct Reader<'a> {
+    text: &'a str,
+    cursor: usize,
+    /// The number of elements the cursor is in
+    depth: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn rest(&self) -> &'a str {
+        &self.text[self.cursor..]
+    }
+
+    fn skip(&mut self, expected: &str) -> bool {
+        if self.rest().starts_with(expected) {
+            self.cursor += expected.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), BMFontError> {
+        if self.skip(expected) {
+            Ok(())
+        } else {
+            syntax_err(format!("Expected \"{}\" in XML", expected))
+        }
+    }
+
+    /// Skips everything up to and including `end`.
+    fn skip_past(&mut self, end: &str) -> Result<(), BMFontError> {
+        match self.rest().find(end) {
+            Some(idx) => {
+                self.cursor += idx + end.len();
+                Ok(())
+            }
+            None => syntax_err(format!("Could not find \"{}\" in XML", end)),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.cursor += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skips whitespace, comments, processing instructions and doctypes.
+    fn skip_misc(&mut self) -> Result<(), BMFontError> {
+        loop {
+            self.skip_whitespace();
+            if self.skip("<!--") {
+                self.skip_past("-->")?;
+            } else if self.skip("<?") {
+                self.skip_past("?>")?;
+            } else if self.skip("<!") {
+                self.skip_past(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<String, BMFontError> {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| c.is_whitespace() || "/>=".contains(c))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            syntax_err("Expected a name in XML")
+        } else {
+            self.cursor += len;
+            Ok(rest[..len].to_owned())
+        }
+    }
+
+    fn element(&mut self) -> Result<Element, BMFontError> {
+        if self.depth == MAX_DEPTH {
+            return syntax_err(format!("XML elements are nested deeper than {}", MAX_DEPTH));
+        }
+        self.depth += 1;
+        let element = self.element_contents();
+        self.depth -= 1;
+        element
+    }
+
+    fn element_contents(&mut self) -> Result<Element, BMFontError> {
+        self.expect("<")?;
+        let mut element = Element {
+            name: self.name()?,
+            attributes: Vec::new(),
+            children: Vec::new(),
+        };
+
+        loop {
+            self.skip_whitespace();
+            if self.skip("/>") {
+                return Ok(element);
+            } else if self.skip(">") {
+                break;
+            }
+            let key = self.name()?;
+            self.skip_whitespace();
+            self.expect("=")?;
+            self.skip_whitespace();
+            let value = self.attribute_value()?;
+            element.attributes.push((key, value));
+        }
+
+        loop {
+            match self.rest().find('<') {
+                Some(idx) => self.cursor += idx,
+                None => return syntax_err(format!("Element <{}> is never closed", element.name)),
+            }
+            if self.skip("</") {
+                let name = self.name()?;
+                if name != element.name {
+                    return syntax_err(format!(
+                        "Expected </{}> in XML, found </{}>",
+                        element.name, name
+                    ));
+                }
+                self.skip_whitespace();
+                self.expect(">")?;
+                return Ok(element);
+            } else if self.skip("<!--") {
+                self.skip_past("-->")?;
+            } else if self.skip("<![CDATA[") {
+                self.skip_past("]]>")?;
+            } else if self.skip("<?") {
+                self.skip_past("?>")?;
+            } else {
+                element.children.push(self.element()?);
+            }
+        }
+    }
+
+    fn attribute_value(&mut self) -> Result<String, BMFontError> {
+        let quote = if self.skip("\"") {
+            '"'
+        } else if self.skip("'") {
+            '\''
+        } else {
+            return syntax_err("Expected a quoted attribute value in XML");
+        };
+        let rest = self.rest();
+        match rest.find(quote) {
+            Some(len) => {
+                self.cursor += len + 1;
+                unescape(&rest[..len])
+            }
+            None => syntax_err("Unterminated attribute value in XML"),
+        }
+    }
+}
+
+/// Replaces the predefined and numeric character entities in `text`.
+fn unescape(text: &str) -> Result<String, BMFontError> {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(idx) = rest.find('&') {
+        result += &rest[..idx];
+        rest = &rest[idx + 1..];
+        let end = match rest.find(';') {
+            Some(end) => end,
+            None => return syntax_err("Unterminated entity in XML"),
+        };
+        let entity = &rest[..end];
+        let character = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ if entity.starts_with("#x") => u32::from_str_radix(&entity[2..], 16)
+                .ok()
+                .and_then(std::char::from_u32),
+            _ if entity.starts_with('#') => entity[1..].parse().ok().and_then(std::char::from_u32),
+            _ => None,
+        };
+        match character {
+            Some(c) => result.push(c),
+            None => return syntax_err(format!("Unknown entity \"&{};\" in XML", entity)),
+        }
+        rest = &rest[end + 1..];
+    }
+    result += rest;
+    Ok(result)
+}
+
+/// Escapes `text` for use in a double-quoted attribute value.
+pub(crate) fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => result += "&amp;",
+            '<' => result += "&lt;",
+            '>' => result += "&gt;",
+            '"' => result += "&quot;",
+            c => result.push(c),
+        }
+    }
+    result
+}
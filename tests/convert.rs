@@ -0,0 +1,148 @@
+use bmfont_parser::{BMFont, Format};
+
+use std::env;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+fn convert(args: &[&str]) -> bool {
+    Command::new(env!("CARGO_BIN_EXE_bmfont-convert"))
+        .args(args)
+        .status()
+        .expect("Failed to run bmfont-convert")
+        .success()
+}
+
+/// Returns `path` as an absolute path without `.` and `..` components. The page images do not
+/// exist, so they can not be canonicalized.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in env::current_dir().unwrap().join(path).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+fn output_dir(name: &str) -> PathBuf {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("nested")).unwrap();
+    dir
+}
+
+#[test]
+fn convert_between_formats() {
+    let dir = output_dir("convert_between_formats");
+    let steps = [
+        ("examples/fonts/iosevka.sfl", "a.fnt", None, Format::BMFont),
+        ("a.fnt", "nested/b.xml", None, Format::BMFontXml),
+        ("nested/b.xml", "c.json", None, Format::BMFontJson),
+        (
+            "c.json",
+            "nested/d.fnt",
+            Some("binary"),
+            Format::BMFontBinary,
+        ),
+        ("nested/d.fnt", "e.sfl", None, Format::SFL),
+    ];
+    let original = BMFont::from_path(&Format::SFL, "examples/fonts/iosevka.sfl").unwrap();
+
+    for (input, output, to, format) in steps.iter() {
+        let input = if input.starts_with("examples") {
+            PathBuf::from(input)
+        } else {
+            dir.join(input)
+        };
+        let output = dir.join(output);
+        let mut args = vec![input.to_str().unwrap(), output.to_str().unwrap()];
+        if let Some(to) = to {
+            args.extend_from_slice(&["--to", to]);
+        }
+        assert!(
+            convert(&args),
+            "Failed to convert into {}",
+            output.display()
+        );
+
+        let contents = fs::read(&output).unwrap();
+        assert_eq!(Format::detect(&contents), Some(*format));
+        let font = BMFont::from_path(format, &output).unwrap();
+        assert_eq!(font.chars, original.chars);
+        assert_eq!(
+            normalize(&font.pages[0].image_path),
+            normalize(Path::new("examples/fonts/iosevka.png"))
+        );
+    }
+}
+
+#[test]
+fn relative_page_paths() {
+    let dir = output_dir("relative_page_paths");
+    let output = dir.join("nested/iosevka.fnt");
+    assert!(convert(&[
+        "examples/fonts/iosevka.fnt",
+        output.to_str().unwrap()
+    ]));
+    let fnt = fs::read_to_string(&output).unwrap();
+    let file = fnt
+        .split("file=\"")
+        .nth(1)
+        .unwrap()
+        .split('"')
+        .next()
+        .unwrap();
+    assert!(Path::new(file).is_relative());
+    assert!(file.ends_with("examples/fonts/iosevka.png"));
+    let font = BMFont::from_path(&Format::BMFont, &output).unwrap();
+    assert_eq!(
+        normalize(&font.pages[0].image_path),
+        normalize(Path::new("examples/fonts/iosevka.png"))
+    );
+}
+
+#[test]
+fn unknown_output_format() {
+    let dir = output_dir("unknown_output_format");
+    let output = dir.join("iosevka.png");
+    assert!(!convert(&[
+        "examples/fonts/iosevka.fnt",
+        output.to_str().unwrap()
+    ]));
+    assert!(!output.exists());
+}
+
+#[test]
+fn lossy_conversion_fails() {
+    let dir = output_dir("lossy_conversion_fails");
+    let input = dir.join("kerned.fnt");
+    fs::write(
+        &input,
+        "info face=\"A\" size=8\ncommon lineHeight=8 base=6 scaleW=8 scaleH=8 pages=1\npage id=0 file=\"a.png\"\nchars count=0\nkernings count=1\nkerning first=65 second=86 amount=-1\n",
+    )
+    .unwrap();
+    let output = dir.join("kerned.sfl");
+    assert!(!convert(&[
+        input.to_str().unwrap(),
+        output.to_str().unwrap()
+    ]));
+    assert!(!output.exists());
+}
+
+#[test]
+fn help_is_printed_to_stdout() {
+    for flag in ["-h", "--help"].iter() {
+        let output = Command::new(env!("CARGO_BIN_EXE_bmfont-convert"))
+            .arg(flag)
+            .output()
+            .expect("Failed to run bmfont-convert");
+        assert!(output.status.success());
+        assert!(String::from_utf8(output.stdout).unwrap().contains("Usage:"));
+        assert!(output.stderr.is_empty());
+    }
+}